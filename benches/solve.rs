@@ -0,0 +1,51 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use sudoku::SudokuBoard;
+
+/// The boards from the `test_solve_hard_*` tests, plus two 17-clue puzzles.
+const PUZZLES: [(&str, &str); 6] = [
+    (
+        "hard",
+        "2..5.74.6....31.........23.....2....86.31.....45........9...7....695...2..1..6..8",
+    ),
+    (
+        "hard_2",
+        "..65.....7.5..23...3.....8..5..96.7.1.4.....8...82.....2.....9...72..4.......75..",
+    ),
+    (
+        "hard_3",
+        ".293.84565782.61.9...1.5.7.3.5.2.6.......9.4..91.67....3..5.........29.39.7....24",
+    ),
+    (
+        "hard_4",
+        "5.8427....4..1.7..19...3..2....6...57.....2..6.513.9..9....15......4..2..7......8",
+    ),
+    (
+        "17_clue",
+        "..............3.85..1.2.......5.7.....4...1...9.......5......73..2.1........4...9",
+    ),
+    (
+        "17_clue_2",
+        ".......1.4.........2...........5.4.7..8...3....1.9....3..4..2...5.1........8.6...",
+    ),
+];
+
+fn solve(c: &mut Criterion) {
+    let mut group = c.benchmark_group("solve");
+    for (name, puzzle) in PUZZLES {
+        let board: SudokuBoard = puzzle.parse().unwrap();
+        // node counts don't vary between runs, so one search is enough
+        let (solution, stats) = board.solve_with_stats();
+        assert!(solution.is_some(), "{name} has no solution");
+        eprintln!(
+            "{name}: {} nodes, {} backtracks",
+            stats.nodes_expanded, stats.backtracks
+        );
+        group.bench_with_input(BenchmarkId::from_parameter(name), &board, |b, board| {
+            b.iter(|| black_box(board).solve())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, solve);
+criterion_main!(benches);
@@ -0,0 +1,99 @@
+//! C bindings, taking and returning boards as 81-byte character buffers.
+//!
+//! The crate only builds as a Rust library by default. Build a library to
+//! link from C with
+//! `cargo rustc --release --lib --features cffi --crate-type staticlib` (or
+//! `cdylib` for a shared one).
+#![allow(unsafe_code)]
+
+use std::{
+    ffi::{c_char, c_int},
+    slice, str,
+};
+
+use crate::{SudokuBoard, CELLS};
+
+/// The solution was written to the output buffer.
+pub const SUDOKU_OK: c_int = 0;
+/// The puzzle isn't a valid 81-character board.
+pub const SUDOKU_INVALID_PUZZLE: c_int = 1;
+/// The puzzle parsed but has no solution.
+pub const SUDOKU_NO_SOLUTION: c_int = 2;
+/// One of the pointers was null.
+pub const SUDOKU_NULL_POINTER: c_int = -1;
+
+/// Solves the puzzle in `puzzle81`, writing the solution to `out_solution81`.
+///
+/// `puzzle81` must point to at least 81 readable bytes, one per cell in
+/// reading order: `1` to `9` for givens and `.`, `0` or a space for empty
+/// cells. Only the first 81 bytes are read, so no NUL terminator is needed.
+///
+/// `out_solution81` must point to at least 81 writable bytes. On success the
+/// solution is written there as 81 ASCII digits with no NUL terminator; on
+/// failure the buffer is left untouched. Nothing is allocated on the caller's
+/// behalf.
+///
+/// Returns [`SUDOKU_OK`], [`SUDOKU_INVALID_PUZZLE`], [`SUDOKU_NO_SOLUTION`]
+/// or [`SUDOKU_NULL_POINTER`].
+///
+/// # Safety
+///
+/// Both pointers must be valid for the 81 bytes described above, and the
+/// buffers must not overlap.
+#[no_mangle]
+pub unsafe extern "C" fn sudoku_solve(
+    puzzle81: *const c_char,
+    out_solution81: *mut c_char,
+) -> c_int {
+    if puzzle81.is_null() || out_solution81.is_null() {
+        return SUDOKU_NULL_POINTER;
+    }
+    // SAFETY: the caller guarantees 81 readable bytes behind `puzzle81`
+    let puzzle = unsafe { slice::from_raw_parts(puzzle81.cast::<u8>(), CELLS) };
+    let Some(board) = str::from_utf8(puzzle)
+        .ok()
+        .and_then(|puzzle| puzzle.parse::<SudokuBoard>().ok())
+    else {
+        return SUDOKU_INVALID_PUZZLE;
+    };
+    let Some(solution) = board.solve() else {
+        return SUDOKU_NO_SOLUTION;
+    };
+
+    // SAFETY: the caller guarantees 81 writable bytes behind `out_solution81`
+    let out = unsafe { slice::from_raw_parts_mut(out_solution81.cast::<u8>(), CELLS) };
+    for (out, space) in out.iter_mut().zip(solution.iter()) {
+        let space = space.expect("solutions have every cell filled");
+        *out = b'0' + usize::from(space) as u8;
+    }
+    SUDOKU_OK
+}
+
+#[test]
+fn test_sudoku_solve() {
+    use std::ptr;
+
+    let puzzle =
+        b"53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+    let mut out = [0u8; CELLS];
+    let result = unsafe { sudoku_solve(puzzle.as_ptr().cast(), out.as_mut_ptr().cast()) };
+    assert_eq!(result, SUDOKU_OK);
+    assert_eq!(
+        &out,
+        b"534678912672195348198342567859761423426853791713924856961537284287419635345286179"
+    );
+
+    let mut out = [0u8; CELLS];
+    let mut unsolvable = *puzzle;
+    unsolvable[2] = b'5';
+    let result = unsafe { sudoku_solve(unsolvable.as_ptr().cast(), out.as_mut_ptr().cast()) };
+    assert_eq!(result, SUDOKU_NO_SOLUTION);
+    assert_eq!(out, [0; CELLS]);
+
+    let mut invalid = *puzzle;
+    invalid[0] = b'x';
+    let result = unsafe { sudoku_solve(invalid.as_ptr().cast(), out.as_mut_ptr().cast()) };
+    assert_eq!(result, SUDOKU_INVALID_PUZZLE);
+    let result = unsafe { sudoku_solve(ptr::null(), out.as_mut_ptr().cast()) };
+    assert_eq!(result, SUDOKU_NULL_POINTER);
+}
@@ -0,0 +1,272 @@
+use crate::{Space, SudokuBoard, BOX_SIZE};
+
+/// Every placement satisfies four constraints: its cell is filled, and its
+/// value appears in its row, its column and its square.
+const CONSTRAINTS: usize = 4;
+const ROOT: usize = 0;
+
+/// The exact cover matrix of a sudoku with boxes `box_size` cells wide, as a
+/// toroidal doubly linked list. Node 0 is the root, nodes `1..=columns` head
+/// the columns, and each placement has one node per constraint after that.
+struct Links {
+    /// Values, and cells along a row or column.
+    side: usize,
+    columns: usize,
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    /// The column header each node sits under.
+    column: Vec<usize>,
+    /// The placement each node belongs to, as `cell * side + value index`.
+    placement: Vec<usize>,
+    /// Nodes remaining in each column.
+    size: Vec<usize>,
+}
+
+impl Links {
+    fn new(box_size: usize) -> Self {
+        let side = box_size * box_size;
+        let cells = side * side;
+        let columns = CONSTRAINTS * cells;
+        let placements = cells * side;
+        let nodes = 1 + columns + placements * CONSTRAINTS;
+        let mut links = Links {
+            side,
+            columns,
+            left: Vec::with_capacity(nodes),
+            right: Vec::with_capacity(nodes),
+            up: Vec::with_capacity(nodes),
+            down: Vec::with_capacity(nodes),
+            column: Vec::with_capacity(nodes),
+            placement: Vec::with_capacity(nodes),
+            size: vec![0; 1 + columns],
+        };
+        for node in 0..=columns {
+            links.left.push(if node == 0 { columns } else { node - 1 });
+            links.right.push(if node == columns { 0 } else { node + 1 });
+            links.up.push(node);
+            links.down.push(node);
+            links.column.push(node);
+            links.placement.push(usize::MAX);
+        }
+
+        for placement in 0..placements {
+            let (cell, value) = (placement / side, placement % side);
+            let (x, y) = (cell % side, cell / side);
+            let square = (y / box_size) * box_size + x / box_size;
+            let columns = [
+                cell,
+                cells + y * side + value,
+                2 * cells + x * side + value,
+                3 * cells + square * side + value,
+            ];
+            let first = links.left.len();
+            for (i, column) in columns.into_iter().enumerate() {
+                let header = 1 + column;
+                let node = first + i;
+                links.left.push(if i == 0 {
+                    first + CONSTRAINTS - 1
+                } else {
+                    node - 1
+                });
+                links.right.push(if i == CONSTRAINTS - 1 {
+                    first
+                } else {
+                    node + 1
+                });
+                links.up.push(links.up[header]);
+                links.down.push(header);
+                let last = links.up[header];
+                links.down[last] = node;
+                links.up[header] = node;
+                links.column.push(header);
+                links.placement.push(placement);
+                links.size[header] += 1;
+            }
+        }
+        links
+    }
+
+    /// The first node of `placement`'s row.
+    fn row(&self, placement: usize) -> usize {
+        1 + self.columns + placement * CONSTRAINTS
+    }
+
+    fn is_covered(&self, header: usize) -> bool {
+        self.right[self.left[header]] != header
+    }
+
+    fn cover(&mut self, header: usize) {
+        self.right[self.left[header]] = self.right[header];
+        self.left[self.right[header]] = self.left[header];
+        let mut row = self.down[header];
+        while row != header {
+            let mut node = self.right[row];
+            while node != row {
+                self.down[self.up[node]] = self.down[node];
+                self.up[self.down[node]] = self.up[node];
+                self.size[self.column[node]] -= 1;
+                node = self.right[node];
+            }
+            row = self.down[row];
+        }
+    }
+
+    fn uncover(&mut self, header: usize) {
+        let mut row = self.up[header];
+        while row != header {
+            let mut node = self.left[row];
+            while node != row {
+                self.size[self.column[node]] += 1;
+                self.down[self.up[node]] = node;
+                self.up[self.down[node]] = node;
+                node = self.left[node];
+            }
+            row = self.up[row];
+        }
+        self.right[self.left[header]] = header;
+        self.left[self.right[header]] = header;
+    }
+
+    /// Adds every exact cover extending `chosen` to `solutions`, stopping
+    /// once there are `limit` of them.
+    fn search(&mut self, chosen: &mut Vec<usize>, solutions: &mut Vec<Vec<usize>>, limit: usize) {
+        if self.right[ROOT] == ROOT {
+            solutions.push(chosen.clone());
+            return;
+        }
+
+        // the column with the fewest rows left keeps the tree narrow
+        let mut header = self.right[ROOT];
+        let mut node = self.right[header];
+        while node != ROOT {
+            if self.size[node] < self.size[header] {
+                header = node;
+            }
+            node = self.right[node];
+        }
+
+        self.cover(header);
+        let mut row = self.down[header];
+        while row != header && solutions.len() < limit {
+            chosen.push(self.placement[row]);
+            let mut node = self.right[row];
+            while node != row {
+                self.cover(self.column[node]);
+                node = self.right[node];
+            }
+            self.search(chosen, solutions, limit);
+            let mut node = self.left[row];
+            while node != row {
+                self.uncover(self.column[node]);
+                node = self.left[node];
+            }
+            chosen.pop();
+            row = self.down[row];
+        }
+        self.uncover(header);
+    }
+}
+
+/// Up to `limit` completions of a sudoku with boxes `box_size` cells wide,
+/// found by Algorithm X. `givens` holds each cell's value index, if any, in
+/// reading order, and so does every solution.
+pub(crate) fn exact_cover(
+    box_size: usize,
+    givens: impl IntoIterator<Item = Option<usize>>,
+    limit: usize,
+) -> Vec<Vec<usize>> {
+    let mut links = Links::new(box_size);
+    let side = links.side;
+    let mut chosen = Vec::new();
+    for (cell, value) in givens.into_iter().enumerate() {
+        let Some(value) = value else {
+            continue;
+        };
+        let placement = cell * side + value;
+        let first = links.row(placement);
+        let mut headers = vec![links.column[first]];
+        let mut node = links.right[first];
+        while node != first {
+            headers.push(links.column[node]);
+            node = links.right[node];
+        }
+        // a given whose constraint is already met contradicts another given
+        if headers.iter().any(|&header| links.is_covered(header)) {
+            return Vec::new();
+        }
+        for header in headers {
+            links.cover(header);
+        }
+        chosen.push(placement);
+    }
+
+    let mut solutions = Vec::new();
+    links.search(&mut chosen, &mut solutions, limit);
+    solutions
+        .into_iter()
+        .map(|placements| {
+            let mut values = vec![0; side * side];
+            for placement in placements {
+                values[placement / side] = placement % side;
+            }
+            values
+        })
+        .collect()
+}
+
+/// Up to `limit` completions of `board`, found by Algorithm X.
+fn dlx_solutions(board: &SudokuBoard, limit: usize) -> Vec<SudokuBoard> {
+    let givens = board.iter().map(|space| space.map(|space| space.idx()));
+    exact_cover(BOX_SIZE, givens, limit)
+        .into_iter()
+        .map(|values| {
+            let mut solution = SudokuBoard::empty();
+            for (cell, value) in values.into_iter().enumerate() {
+                solution.0[cell] = Some(Space::all()[value]);
+            }
+            solution
+        })
+        .collect()
+}
+
+impl SudokuBoard {
+    /// Solves the board as an exact cover problem with Knuth's dancing links,
+    /// which is much faster than [`SudokuBoard::solve`] on the hardest
+    /// puzzles. Returns `None` if there is no solution.
+    pub fn solve_dlx(&self) -> Option<SudokuBoard> {
+        dlx_solutions(self, 1).pop()
+    }
+}
+
+#[test]
+fn test_solve_dlx() {
+    // the test_solve_hard boards
+    for board_str in [
+        "2..5.74.6....31.........23.....2....86.31.....45........9...7....695...2..1..6..8",
+        "..65.....7.5..23...3.....8..5..96.7.1.4.....8...82.....2.....9...72..4.......75..",
+        ".293.84565782.61.9...1.5.7.3.5.2.6.......9.4..91.67....3..5.........29.39.7....24",
+        "5.8427....4..1.7..19...3..2....6...57.....2..6.513.9..9....15......4..2..7......8",
+    ] {
+        let board: SudokuBoard = board_str.parse().unwrap();
+        let solution = board.solve_dlx().expect("Sudoku board has a solution");
+        assert_eq!(solution.validate(), Ok(()));
+        assert_eq!(
+            dlx_solutions(&board, 2),
+            board.solutions().take(2).collect::<Vec<_>>()
+        );
+        assert_eq!(Some(solution), board.solve());
+    }
+
+    let solutions = dlx_solutions(&SudokuBoard::empty(), 5);
+    assert_eq!(solutions.len(), 5);
+    assert!(solutions
+        .iter()
+        .all(|solution| solution.validate() == Ok(()) && solution.empty_count() == 0));
+
+    let mut conflicting = SudokuBoard::empty();
+    conflicting[(0, 0)] = Some(Space::One);
+    conflicting[(8, 0)] = Some(Space::One);
+    assert_eq!(conflicting.solve_dlx(), None);
+}
@@ -0,0 +1,246 @@
+use crate::{BoardPosition, Pos, PossibilitySpaceBoard, Rules, Space, SudokuBoard, SudokuRegion};
+
+/// A single placement that follows logically from the current board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Hint {
+    pub pos: BoardPosition,
+    pub space: Space,
+    pub reason: HintReason,
+}
+
+/// Why a [`Hint`]'s placement is forced.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HintReason {
+    /// Every other value is already taken by a cell in the same row, column
+    /// or square.
+    OnlyCandidate,
+    /// No other cell in the region can take the value.
+    OnlyPlace(SudokuRegion),
+}
+
+impl SudokuBoard {
+    /// Finds the next placement forced by a single candidate or a single
+    /// place in some region, without solving the rest of the board.
+    pub fn hint(&self) -> Option<Hint> {
+        find_single(Rules::classic(), self, &self.candidates())
+    }
+
+    /// Every empty cell that is currently a naked or hidden single, with the
+    /// value it is forced to, leaving the board as it is. Unlike repeatedly
+    /// applying [`SudokuBoard::hint`], placements that only become forced
+    /// after these are made aren't included.
+    pub fn forced_cells(&self) -> Vec<(BoardPosition, Space)> {
+        let candidates = self.candidates();
+        self.iter_empty()
+            .filter_map(|pos| single_at(Rules::classic(), &candidates, pos))
+            .map(|hint| (hint.pos, hint.space))
+            .collect()
+    }
+
+    /// Every empty cell left with a single candidate once the values of its
+    /// row, column and square are ruled out, with that value. Cells forced only
+    /// by being the one place for a value in a region aren't included.
+    pub fn naked_singles(&self) -> Vec<(BoardPosition, Space)> {
+        self.iter_empty()
+            .filter_map(|pos| Some((pos, self.candidates_at(Pos::of(pos)).only()?)))
+            .collect()
+    }
+
+    /// Every empty cell that is the only place left for some value within one
+    /// of its regions, with that value and the first region that forces it.
+    /// Cells down to a single candidate are included only if a region forces
+    /// them too.
+    pub fn hidden_singles(&self) -> Vec<(BoardPosition, Space, SudokuRegion)> {
+        let candidates = self.candidates();
+        self.iter_empty()
+            .filter_map(|pos| {
+                let (space, region) = hidden_single_at(Rules::classic(), &candidates, pos)?;
+                Some((pos, space, region))
+            })
+            .collect()
+    }
+
+    /// Candidates for every cell, ruling out only values already placed in the
+    /// same row, column or square.
+    pub(crate) fn candidates(&self) -> PossibilitySpaceBoard {
+        PossibilitySpaceBoard::from_fn(|pos| self.candidates_at(Pos::of(pos)))
+    }
+}
+
+/// First empty cell that has a single candidate, or is the only place for one
+/// of its candidates within a region.
+pub(crate) fn find_single(
+    rules: &Rules,
+    board: &SudokuBoard,
+    possibilities_board: &PossibilitySpaceBoard,
+) -> Option<Hint> {
+    board
+        .iter_empty()
+        .find_map(|pos| single_at(rules, possibilities_board, pos))
+}
+
+/// The placement forced at `pos` by a single candidate or a single place in
+/// one of its regions, if any.
+fn single_at(
+    rules: &Rules,
+    possibilities_board: &PossibilitySpaceBoard,
+    pos: BoardPosition,
+) -> Option<Hint> {
+    let candidates = possibilities_board[pos];
+    if let Some(space) = candidates.only() {
+        return Some(Hint {
+            pos,
+            space,
+            reason: HintReason::OnlyCandidate,
+        });
+    }
+    let (space, region) = hidden_single_at(rules, possibilities_board, pos)?;
+    Some(Hint {
+        pos,
+        space,
+        reason: HintReason::OnlyPlace(region),
+    })
+}
+
+/// The value that can only go at `pos` within one of its regions, with the
+/// first such region, if any.
+fn hidden_single_at(
+    rules: &Rules,
+    possibilities_board: &PossibilitySpaceBoard,
+    pos: BoardPosition,
+) -> Option<(Space, SudokuRegion)> {
+    rules.houses_of(pos).find_map(|(region, cells)| {
+        let mut solo_candidates = possibilities_board[pos];
+        for &other in cells.iter().filter(|p| *p != &pos) {
+            solo_candidates = solo_candidates & !possibilities_board[other];
+        }
+        solo_candidates.only().map(|space| (space, region))
+    })
+}
+
+#[test]
+fn test_hint_only_candidate() {
+    let solved = crate::easy_solution();
+    let mut board = solved.clone();
+    board[(4, 4)] = None;
+    assert_eq!(
+        board.hint(),
+        Some(Hint {
+            pos: (4, 4),
+            space: Space::Five,
+            reason: HintReason::OnlyCandidate,
+        })
+    );
+    assert_eq!(solved.hint(), None);
+}
+
+#[test]
+fn test_hint_matches_solution() {
+    let board = crate::easy_puzzle();
+    let solution = crate::easy_solution();
+    let hint = board.hint().expect("board has a forced placement");
+    assert_eq!(board[hint.pos], None);
+    assert_eq!(solution[hint.pos], Some(hint.space));
+}
+
+#[test]
+fn test_forced_cells() {
+    let mut board = crate::easy_puzzle();
+    let solution = crate::easy_solution();
+    for _ in 0..10 {
+        let hint = board.hint().expect("board has a forced placement");
+        board[hint.pos] = Some(hint.space);
+    }
+
+    let before = board.clone();
+    let forced = board.forced_cells();
+    assert_eq!(board, before);
+    let hint = board.hint().unwrap();
+    assert_eq!(forced.first(), Some(&(hint.pos, hint.space)));
+    assert!(forced.len() > 1);
+    for &(pos, space) in &forced {
+        assert_eq!(board[pos], None);
+        assert_eq!(solution[pos], Some(space));
+    }
+
+    assert_eq!(solution.forced_cells(), vec![]);
+}
+
+#[test]
+fn test_naked_singles() {
+    let solved = crate::easy_solution();
+    let mut board = solved.clone();
+    board[(4, 4)] = None;
+    assert_eq!(board.naked_singles(), vec![((4, 4), Space::Five)]);
+    assert_eq!(solved.naked_singles(), vec![]);
+
+    let board = crate::easy_puzzle();
+    let solution = crate::easy_solution();
+    let naked = board.naked_singles();
+    let forced = board.forced_cells();
+    assert_eq!(
+        naked,
+        vec![
+            ((4, 4), Space::Five),
+            ((5, 6), Space::Seven),
+            ((8, 6), Space::Four),
+            ((7, 7), Space::Three),
+        ]
+    );
+    // the rest are only places for their values
+    assert!(naked.len() < forced.len());
+    for &(pos, space) in &naked {
+        assert!(forced.contains(&(pos, space)));
+        assert_eq!(board.candidates_at(Pos::of(pos)).len(), 1);
+        assert_eq!(solution[pos], Some(space));
+    }
+}
+
+#[test]
+fn test_hidden_singles() {
+    use SudokuRegion::*;
+
+    let board = crate::easy_puzzle();
+    let solution = crate::easy_solution();
+    let hidden = board.hidden_singles();
+    assert_eq!(
+        hidden,
+        vec![
+            ((5, 0), Space::Eight, Column(5)),
+            ((7, 0), Space::One, Column(7)),
+            ((4, 2), Space::Four, Column(4)),
+            ((6, 2), Space::Five, Row(2)),
+            ((2, 4), Space::Six, Row(4)),
+            ((6, 4), Space::Seven, Row(4)),
+            ((2, 5), Space::Three, Row(5)),
+            ((6, 5), Space::Eight, Row(5)),
+            ((0, 6), Space::Nine, Column(0)),
+            ((1, 7), Space::Eight, Row(7)),
+            ((6, 7), Space::Six, Row(7)),
+            ((6, 8), Space::One, Square(8)),
+        ]
+    );
+    // some are hidden behind other candidates, not just naked singles
+    assert!(hidden
+        .iter()
+        .any(|&(pos, _, _)| board.candidates_at(Pos::of(pos)).len() > 1));
+    for &(pos, space, region) in &hidden {
+        assert!(region.contains(pos));
+        assert!(region
+            .into_iter()
+            .filter(|&other| other != pos && board[other].is_none())
+            .all(|other| !board.candidates_at(Pos::of(other))[space]));
+        assert_eq!(solution[pos], Some(space));
+    }
+
+    let naked = board.naked_singles();
+    for (pos, space) in board.forced_cells() {
+        assert!(
+            naked.contains(&(pos, space))
+                || hidden
+                    .iter()
+                    .any(|&hidden| hidden.0 == pos && hidden.1 == space)
+        );
+    }
+    assert_eq!(solution.hidden_singles(), vec![]);
+}
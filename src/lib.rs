@@ -0,0 +1,2879 @@
+//! Simple sudoku solver.
+#![cfg_attr(not(feature = "cffi"), forbid(unsafe_code))]
+#![cfg_attr(feature = "cffi", deny(unsafe_code))]
+use std::{
+    array,
+    error::Error,
+    fmt::Display,
+    io::{self, BufRead, IsTerminal},
+    iter::empty,
+    ops::{BitAnd, BitOr, Index, IndexMut, Not},
+    str::FromStr,
+    time::Duration,
+};
+
+#[cfg(feature = "chrono")]
+use chrono::{Datelike, NaiveDate};
+use log::{trace, warn};
+use rand::{seq::SliceRandom, Rng};
+pub use space_search;
+use space_search::{Scoreable, Searchable, SolutionIdentifiable};
+
+#[cfg(feature = "cffi")]
+pub mod cffi;
+mod dlx;
+mod hint;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "image")]
+mod raster;
+mod render;
+mod rules;
+#[cfg(feature = "serde")]
+mod serialize;
+mod sized;
+mod solver;
+mod steps;
+mod techniques;
+mod transform;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub use hint::{Hint, HintReason};
+pub use rules::{Cage, Constraint, Parity, Rules};
+pub use sized::{ParseSizedBoardError, SizedBoard, MAX_BOX_SIZE, MIN_BOX_SIZE};
+pub use solver::{NodeLimitExceeded, SearchStats, Solver, ValueOrder};
+pub use steps::{SolveStep, StepAction, StepReason};
+pub use techniques::{Difficulty, Technique};
+
+/// Width and height of a single box of a [`SudokuBoard`]. Other sizes are
+/// played on a [`SizedBoard`].
+pub const BOX_SIZE: usize = 3;
+/// Width and height of the whole board, and the number of distinct values.
+pub const SIDE: usize = BOX_SIZE * BOX_SIZE;
+/// Total number of cells on the board.
+pub const CELLS: usize = SIDE * SIDE;
+/// Puzzles [`SudokuBoard::generate_rated`] tries before settling for the
+/// closest rating.
+pub const GENERATE_ATTEMPTS: usize = 100;
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct Board<Cell>([Cell; CELLS]);
+
+impl<Cell> Board<Cell> {
+    pub fn iter(&self) -> impl Iterator<Item = &Cell> {
+        self.0.iter()
+    }
+
+    pub fn iter_positions() -> impl Iterator<Item = BoardPosition> {
+        (0..CELLS).map(|i| (i % SIDE, i / SIDE))
+    }
+
+    /// Builds a board by calling `f` with each position in reading order.
+    pub fn from_fn(mut f: impl FnMut(BoardPosition) -> Cell) -> Self {
+        Board(array::from_fn(|i| f((i % SIDE, i / SIDE))))
+    }
+
+    /// The 20 other cells sharing a row, column or square with `pos`.
+    pub fn peers(pos: Pos) -> impl Iterator<Item = Pos> {
+        let pos = BoardPosition::from(pos);
+        let square = SudokuRegion::square_of(pos);
+        empty()
+            .chain(SudokuRegion::row_of(pos))
+            .chain(SudokuRegion::column_of(pos))
+            .filter(move |&p| p != pos && !square.contains(p))
+            .chain(square.into_iter().filter(move |&p| p != pos))
+            .map(Pos::of)
+    }
+    /// Cells of row `y`, left to right.
+    pub fn row(&self, y: usize) -> impl Iterator<Item = (BoardPosition, &Cell)> {
+        self.region(Row(y))
+    }
+
+    /// Cells of column `x`, top to bottom.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = (BoardPosition, &Cell)> {
+        self.region(Column(x))
+    }
+
+    /// Cells of the square `bx` squares across and `by` squares down, in
+    /// reading order.
+    pub fn box_at(&self, bx: usize, by: usize) -> impl Iterator<Item = (BoardPosition, &Cell)> {
+        self.region(Square(by * BOX_SIZE + bx))
+    }
+
+    fn region(&self, region: SudokuRegion) -> impl Iterator<Item = (BoardPosition, &Cell)> {
+        region.into_iter().map(move |pos| (pos, &self[pos]))
+    }
+}
+
+pub type BoardPosition = (usize, usize);
+
+/// A cell's coordinates, `x` across and `y` down from the top left, known to
+/// be on the board.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Pos {
+    x: u8,
+    y: u8,
+}
+
+impl Pos {
+    /// The cell `x` across and `y` down, or `None` if that's off the board.
+    pub fn new(x: usize, y: usize) -> Option<Pos> {
+        (x < SIDE && y < SIDE).then(|| Pos::of((x, y)))
+    }
+
+    /// The cell at `index` in reading order, or `None` if that's off the
+    /// board.
+    pub fn from_index(index: usize) -> Option<Pos> {
+        Pos::new(index % SIDE, index / SIDE)
+    }
+
+    /// Every cell, in reading order.
+    pub fn all() -> impl Iterator<Item = Pos> {
+        SudokuBoard::iter_positions().map(Pos::of)
+    }
+
+    pub fn x(self) -> usize {
+        usize::from(self.x)
+    }
+
+    pub fn y(self) -> usize {
+        usize::from(self.y)
+    }
+
+    /// The cell's index in reading order.
+    pub fn index(self) -> usize {
+        self.y() * SIDE + self.x()
+    }
+
+    /// Wraps a position already known to be on the board.
+    fn of((x, y): BoardPosition) -> Pos {
+        debug_assert!(x < SIDE && y < SIDE, "({x}, {y}) is off the board");
+        Pos {
+            x: x as u8,
+            y: y as u8,
+        }
+    }
+}
+
+impl From<Pos> for BoardPosition {
+    fn from(pos: Pos) -> Self {
+        (pos.x(), pos.y())
+    }
+}
+
+impl Display for Pos {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<Cell> Index<BoardPosition> for Board<Cell> {
+    type Output = Cell;
+
+    fn index(&self, (x, y): BoardPosition) -> &Self::Output {
+        &self.0[y * SIDE + x]
+    }
+}
+
+impl<Cell> IndexMut<BoardPosition> for Board<Cell> {
+    fn index_mut(&mut self, (x, y): BoardPosition) -> &mut Self::Output {
+        &mut self.0[y * SIDE + x]
+    }
+}
+
+impl<Cell> Index<Pos> for Board<Cell> {
+    type Output = Cell;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        &self.0[pos.index()]
+    }
+}
+
+impl<Cell> IndexMut<Pos> for Board<Cell> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        &mut self.0[pos.index()]
+    }
+}
+
+#[derive(Clone, Debug, Copy, Hash, PartialEq, Eq)]
+pub enum Space {
+    One,
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+}
+
+impl Space {
+    /// Every value, from one to nine.
+    pub const fn all() -> [Space; SIDE] {
+        use Space::*;
+        [One, Two, Three, Four, Five, Six, Seven, Eight, Nine]
+    }
+
+    fn idx(&self) -> usize {
+        let space_number: usize = (*self).into();
+        space_number - 1
+    }
+}
+
+impl From<Space> for usize {
+    fn from(value: Space) -> Self {
+        use Space::*;
+        match value {
+            One => 1,
+            Two => 2,
+            Three => 3,
+            Four => 4,
+            Five => 5,
+            Six => 6,
+            Seven => 7,
+            Eight => 8,
+            Nine => 9,
+        }
+    }
+}
+
+impl TryFrom<usize> for Space {
+    type Error = String;
+
+    fn try_from(value: usize) -> Result<Self, Self::Error> {
+        use Space::*;
+        let result = match value {
+            1 => One,
+            2 => Two,
+            3 => Three,
+            4 => Four,
+            5 => Five,
+            6 => Six,
+            7 => Seven,
+            8 => Eight,
+            9 => Nine,
+            _ => return Err(format!("Cant convert '{}' to a space value", value)),
+        };
+        Ok(result)
+    }
+}
+
+pub type SudokuBoard = Board<Option<Space>>;
+
+/// The set of values a cell could still take.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SudokuChoices(u16);
+
+impl SudokuChoices {
+    pub fn all() -> Self {
+        SudokuChoices((1 << SIDE) - 1)
+    }
+
+    pub fn none() -> Self {
+        SudokuChoices(0b000000000)
+    }
+
+    pub fn one(space: Space) -> Self {
+        let mut choices = SudokuChoices::none();
+        choices.set(space, true);
+        choices
+    }
+
+    pub fn new(initial_choice: Option<Space>) -> Self {
+        match initial_choice {
+            Some(space) => SudokuChoices::one(space),
+            None => SudokuChoices::all(),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Space> + '_ {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            (bits != 0).then(|| {
+                let i = bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+                Space::all()[i]
+            })
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The remaining candidate, if there is exactly one.
+    pub fn only(&self) -> Option<Space> {
+        (self.len() == 1).then(|| Space::all()[self.0.trailing_zeros() as usize])
+    }
+
+    pub fn set(&mut self, space: Space, value: bool) {
+        if value {
+            self.0 |= 1 << space.idx();
+        } else {
+            self.0 &= !(1 << space.idx());
+        }
+    }
+}
+
+impl Index<Space> for SudokuChoices {
+    type Output = bool;
+
+    fn index(&self, index: Space) -> &Self::Output {
+        // this is stupid, this shouldnt work :/
+        if self.0 & (1 << index.idx()) != 0 {
+            &true
+        } else {
+            &false
+        }
+    }
+}
+
+impl BitAnd for SudokuChoices {
+    type Output = SudokuChoices;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        SudokuChoices(self.0 & rhs.0)
+    }
+}
+
+impl Not for SudokuChoices {
+    type Output = SudokuChoices;
+
+    fn not(self) -> Self::Output {
+        SudokuChoices(!self.0 & SudokuChoices::all().0)
+    }
+}
+
+impl BitOr for SudokuChoices {
+    type Output = SudokuChoices;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        SudokuChoices(self.0 | rhs.0)
+    }
+}
+
+impl Display for SudokuChoices {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{}]",
+            (0..SIDE)
+                .map(|i| if self.0 & (1 << i) != 0 {
+                    (i + 1).to_string()
+                } else {
+                    " ".to_string()
+                })
+                .collect::<Vec<_>>()
+                .join("")
+        )
+    }
+}
+
+/// Candidates for every cell, as given by [`SudokuBoard::pencil_marks`].
+pub type PossibilitySpaceBoard = Board<SudokuChoices>;
+impl PossibilitySpaceBoard {
+    pub fn new(board: &SudokuBoard) -> Self {
+        Board(board.0.map(SudokuChoices::new))
+    }
+
+    /// The candidates of every cell in reading order, as space-separated
+    /// fields of digits such as `137`. A cell with no candidates left is
+    /// written as `-`.
+    pub fn to_candidate_string(&self) -> String {
+        self.iter()
+            .map(|choices| match choices.is_empty() {
+                true => String::from("-"),
+                false => choices
+                    .iter()
+                    .map(|space| usize::from(space).to_string())
+                    .collect(),
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Parses the format written by
+    /// [`PossibilitySpaceBoard::to_candidate_string`]. Fields may be split by
+    /// any whitespace, so one row per line reads just as well.
+    pub fn from_candidate_string(s: &str) -> Result<Self, ParseBoardError> {
+        let fields = s.split_whitespace().collect::<Vec<_>>();
+        if fields.len() != CELLS {
+            return Err(ParseBoardError::WrongCellCount {
+                found: fields.len(),
+            });
+        }
+        let mut board = Board([SudokuChoices::none(); CELLS]);
+        for (i, field) in fields.into_iter().enumerate() {
+            if field == "-" {
+                continue;
+            }
+            for ch in field.chars() {
+                let space = ch
+                    .to_digit(10)
+                    .and_then(|digit| Space::try_from(digit as usize).ok())
+                    .ok_or(ParseBoardError::InvalidChar {
+                        ch,
+                        row: i / SIDE,
+                        col: i % SIDE,
+                    })?;
+                board.0[i].set(space, true);
+            }
+        }
+        Ok(board)
+    }
+}
+
+impl Display for PossibilitySpaceBoard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            (0..SIDE)
+                .map(|y| {
+                    (0..SIDE)
+                        .map(|x| format!("{}", self[(x, y)]))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        )
+    }
+}
+
+/// A row, column or 3x3 square, each of which must hold every value once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SudokuRegion {
+    Column(usize),
+    Row(usize),
+    Square(usize),
+    /// `Diagonal(0)` runs from the top left corner, `Diagonal(1)` from the top
+    /// right. Only a region under [`Constraint::Diagonal`].
+    Diagonal(usize),
+    /// One of the four extra 3x3 windows of [`Constraint::Windoku`], numbered
+    /// in reading order.
+    Window(usize),
+}
+use SudokuRegion::*;
+
+impl SudokuRegion {
+    fn row_of((_, y): BoardPosition) -> SudokuRegion {
+        Row(y)
+    }
+
+    fn column_of((x, _): BoardPosition) -> SudokuRegion {
+        Column(x)
+    }
+
+    fn square_of((x, y): BoardPosition) -> SudokuRegion {
+        Square((y / BOX_SIZE) * BOX_SIZE + (x / BOX_SIZE))
+    }
+
+    /// The top left cell of a [`Window`], one cell in from a corner square.
+    fn window_corner(window: usize) -> BoardPosition {
+        (
+            1 + (window % 2) * (BOX_SIZE + 1),
+            1 + (window / 2) * (BOX_SIZE + 1),
+        )
+    }
+
+    fn all() -> impl Iterator<Item = SudokuRegion> {
+        (0..SIDE).flat_map(|i| [Row(i), Column(i), Square(i)])
+    }
+
+    fn contains(&self, pos: BoardPosition) -> bool {
+        match *self {
+            Column(col) => pos.0 == col,
+            Row(row) => pos.1 == row,
+            Square(square) => SudokuRegion::square_of(pos) == Square(square),
+            Diagonal(0) => pos.0 == pos.1,
+            Diagonal(_) => pos.0 + pos.1 == SIDE - 1,
+            Window(window) => {
+                let (x, y) = SudokuRegion::window_corner(window);
+                (x..x + BOX_SIZE).contains(&pos.0) && (y..y + BOX_SIZE).contains(&pos.1)
+            }
+        }
+    }
+}
+
+impl IntoIterator for SudokuRegion {
+    type Item = BoardPosition;
+
+    type IntoIter = SudokuRegionIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        SudokuRegionIter {
+            region: self,
+            index: Some(0),
+        }
+    }
+}
+
+/// Iterator over the positions in a [`SudokuRegion`].
+pub struct SudokuRegionIter {
+    region: SudokuRegion,
+    index: Option<usize>,
+}
+
+impl Iterator for SudokuRegionIter {
+    type Item = BoardPosition;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.index?;
+        let next_pos = match self.region {
+            Column(col) => (col, index),
+            Row(row) => (index, row),
+            Square(square) => (
+                (square % BOX_SIZE) * BOX_SIZE + (index % BOX_SIZE),
+                (square / BOX_SIZE) * BOX_SIZE + (index / BOX_SIZE),
+            ),
+            Diagonal(0) => (index, index),
+            Diagonal(_) => (SIDE - 1 - index, index),
+            Window(window) => {
+                let (x, y) = SudokuRegion::window_corner(window);
+                (x + index % BOX_SIZE, y + index / BOX_SIZE)
+            }
+        };
+        self.index = (index < SIDE - 1).then_some(index + 1);
+        Some(next_pos)
+    }
+}
+
+/// Outcome of [`SudokuBoard::solve_checked`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolveResult {
+    /// The only solution.
+    Unique(SudokuBoard),
+    /// The first of several solutions found.
+    Multiple(SudokuBoard),
+    NoSolution,
+}
+
+/// Outcome of [`SudokuBoard::propagate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PropagationOutcome {
+    /// Logic filled every cell.
+    Solved,
+    /// Logic ran out of placements with cells still empty, so finishing the
+    /// board takes a guess.
+    Stuck,
+    /// The board can't be solved, as some cell or value has nowhere to go.
+    Contradiction,
+}
+
+/// A pattern the clues of a generated puzzle can be made to follow.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Symmetry {
+    /// Unchanged by a half turn.
+    Rotational180,
+    /// Unchanged by a quarter turn.
+    Rotational90,
+    /// Unchanged by mirroring left to right.
+    Mirror,
+    /// Unchanged by reflecting across the diagonal from the top left.
+    Diagonal,
+}
+
+impl Symmetry {
+    fn apply(self, (x, y): BoardPosition) -> BoardPosition {
+        match self {
+            Symmetry::Rotational180 => (SIDE - 1 - x, SIDE - 1 - y),
+            Symmetry::Rotational90 => (SIDE - 1 - y, x),
+            Symmetry::Mirror => (SIDE - 1 - x, y),
+            Symmetry::Diagonal => (y, x),
+        }
+    }
+
+    /// `pos` and every cell the symmetry carries it to.
+    pub fn orbit(self, pos: BoardPosition) -> Vec<BoardPosition> {
+        let mut orbit = vec![pos];
+        let mut next = self.apply(pos);
+        while next != pos {
+            orbit.push(next);
+            next = self.apply(next);
+        }
+        orbit
+    }
+}
+
+impl SudokuBoard {
+    /// A board with every cell empty.
+    pub fn empty() -> SudokuBoard {
+        Board([None; CELLS])
+    }
+
+    /// Fills in every cell that follows from singles and the other
+    /// techniques, returning the candidates left and whether the board turned
+    /// out to be contradictory.
+    ///
+    /// Unique rectangles and BUG+1 are left out, because on a board with
+    /// several solutions they can rule out real ones, and the search relies
+    /// on reduction keeping every solution. Use [`Solver::with_technique`]
+    /// to turn them on for boards known to have a unique solution.
+    pub fn reduce(&mut self) -> (PossibilitySpaceBoard, bool) {
+        self.reduce_with(Rules::classic(), &techniques::sound())
+    }
+
+    /// The candidates left in every cell once everything that follows
+    /// logically from the board has been filled in, without changing the
+    /// board itself. Filled cells have only their value.
+    pub fn pencil_marks(&self) -> PossibilitySpaceBoard {
+        self.clone().reduce().0
+    }
+
+    /// Fills in every cell that follows logically from the board, without
+    /// guessing, and returns how many were filled. Cells logic can't settle
+    /// stay empty. A board that logic shows to be contradictory is left as it
+    /// was, with nothing filled.
+    pub fn autofill(&mut self) -> usize {
+        let empty = self.empty_count();
+        match self.propagate() {
+            PropagationOutcome::Contradiction => 0,
+            _ => empty - self.empty_count(),
+        }
+    }
+
+    /// Fills in every cell that follows logically from the board, like
+    /// [`SudokuBoard::autofill`], and says whether that solved it, got stuck
+    /// short of a solution, or found a contradiction. A contradictory board is
+    /// left as it was.
+    pub fn propagate(&mut self) -> PropagationOutcome {
+        let mut board = self.clone();
+        let (_, is_invalid) = board.reduce();
+        if is_invalid {
+            return PropagationOutcome::Contradiction;
+        }
+        *self = board;
+        match self.is_complete() {
+            true => PropagationOutcome::Solved,
+            false => PropagationOutcome::Stuck,
+        }
+    }
+
+    /// How many of the [`SudokuBoard::pencil_marks`] each cell has, as rows
+    /// like [`SudokuBoard::to_2d`]. Filled cells count 1, so the lowest count
+    /// above that marks where the puzzle is most constrained.
+    pub fn candidate_counts(&self) -> [[u8; SIDE]; SIDE] {
+        let pencil_marks = self.pencil_marks();
+        array::from_fn(|y| array::from_fn(|x| pencil_marks[(x, y)].len() as u8))
+    }
+
+    fn reduce_with(
+        &mut self,
+        rules: &Rules,
+        techniques: &[Technique],
+    ) -> (PossibilitySpaceBoard, bool) {
+        // every pass that adjusts anything rules out at least one candidate,
+        // so running past this many means a technique is going in circles
+        const MAX_PASSES: usize = CELLS * SIDE;
+        self.reduce_within(rules, techniques, MAX_PASSES)
+    }
+
+    /// Like [`SudokuBoard::reduce_with`], but gives up and reports the board
+    /// as invalid once it has made `max_passes` passes without settling.
+    fn reduce_within(
+        &mut self,
+        rules: &Rules,
+        techniques: &[Technique],
+        max_passes: usize,
+    ) -> (PossibilitySpaceBoard, bool) {
+        fn set(
+            rules: &Rules,
+            board: &mut SudokuBoard,
+            possibilities_board: &mut PossibilitySpaceBoard,
+            pos: BoardPosition,
+            space: Space,
+        ) -> bool {
+            if board[pos].is_some() {
+                unimplemented!(
+                    "Not allowed to change the value of an already set space: {:?} to {:?} at {:?}",
+                    board[pos],
+                    space,
+                    pos
+                );
+            }
+
+            // placements forced by eliminations, handled in a loop rather than
+            // by recursing so long chains can't overflow the stack
+            let mut worklist = vec![(pos, space)];
+            while let Some((pos, space)) = worklist.pop() {
+                if board[pos].is_some() {
+                    // already forced to the same value through another chain
+                    continue;
+                }
+                trace!("placing {space:?} at {pos:?}");
+                board[pos] = Some(space);
+                possibilities_board[pos] = SudokuChoices::one(space);
+
+                for &pos in rules.peers(pos) {
+                    possibilities_board[pos].set(space, false);
+                    let remaining_possibilities =
+                        possibilities_board[pos].iter().take(2).collect::<Vec<_>>();
+                    match remaining_possibilities[..] {
+                        [] => return true,
+                        [only] if board[pos].is_none() => worklist.push((pos, only)),
+                        _ => {}
+                    }
+                }
+            }
+            false
+        }
+
+        let mut possibilities_board = PossibilitySpaceBoard::new(self);
+        rules.prune_parities(&mut possibilities_board);
+
+        let mut is_invalid = false;
+        let mut passes = 0;
+
+        'outer: loop {
+            passes += 1;
+            if passes > max_passes {
+                warn!("reduction didn't settle after {max_passes} passes");
+                is_invalid = true;
+                break;
+            }
+            let mut adjusted = false;
+
+            for pos in SudokuBoard::iter_positions() {
+                let mut new_possibilities = possibilities_board[pos];
+
+                if self[pos].is_none() {
+                    for (_, cells) in rules.houses_of(pos) {
+                        let mut solo_candidates = new_possibilities;
+                        for &pos in cells.iter().filter(|p| *p != &pos) {
+                            if let Some(space) = self[pos] {
+                                new_possibilities.set(space, false);
+                            }
+                            for space in possibilities_board[pos].iter() {
+                                solo_candidates.set(space, false);
+                            }
+                        }
+                        if let Some(value) = solo_candidates.only() {
+                            new_possibilities = SudokuChoices::one(value);
+                            break;
+                        }
+                    }
+
+                    // update possibility space
+                    adjusted |= new_possibilities != possibilities_board[pos];
+                    possibilities_board[pos] = new_possibilities;
+                }
+
+                // confirm square if all alternative possibilities are exhausted
+                let remaining_possibilities = new_possibilities.iter().take(2).collect::<Vec<_>>();
+                match remaining_possibilities[..] {
+                    [] => {
+                        is_invalid = true;
+                    }
+                    [value] if self[pos].is_none() => {
+                        is_invalid |= set(rules, self, &mut possibilities_board, pos, value);
+                    }
+                    _ => {}
+                }
+                if is_invalid {
+                    break 'outer;
+                }
+            }
+
+            if !adjusted {
+                adjusted = rules.prune_cages(&mut possibilities_board);
+            }
+
+            // fall back on more involved techniques once singles stall
+            if !adjusted {
+                adjusted = techniques
+                    .iter()
+                    .any(|technique| technique.apply(rules, self, &mut possibilities_board));
+            }
+
+            if !adjusted || self.is_complete() {
+                break;
+            }
+        }
+
+        trace!("after reduction:\n{possibilities_board}");
+        (possibilities_board, is_invalid)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        Rules::classic().validate(self)
+    }
+
+    /// Whether the board is filled and every row, column and square holds each
+    /// value once. Much cheaper than [`SudokuBoard::validate`] for checking
+    /// many candidate solutions, but gives no reason when it fails.
+    pub fn is_valid_solution(&self) -> bool {
+        SudokuRegion::all().all(|region| {
+            let mut seen = 0u16;
+            for pos in region {
+                match self[pos] {
+                    Some(space) => seen |= 1 << space.idx(),
+                    None => return false,
+                }
+            }
+            seen == (1 << SIDE) - 1
+        })
+    }
+
+    /// Every pair of cells that share a row, column or square and hold the
+    /// same value.
+    pub fn conflicts(&self) -> Vec<(Space, Pos, Pos)> {
+        Rules::classic()
+            .conflicts(self)
+            .into_iter()
+            .map(|(space, a, b)| (space, Pos::of(a), Pos::of(b)))
+            .collect()
+    }
+
+    /// Every cell whose value differs from `other`, in reading order, as
+    /// `(pos, value here, value in other)`.
+    pub fn diff(&self, other: &SudokuBoard) -> Vec<(Pos, Option<Space>, Option<Space>)> {
+        Pos::all()
+            .filter(|&pos| self[pos] != other[pos])
+            .map(|pos| (pos, self[pos], other[pos]))
+            .collect()
+    }
+
+    /// Whether every filled cell here holds the same value in `solution`, so a
+    /// board in progress has no mistakes yet. Empty cells match anything.
+    pub fn is_consistent_with(&self, solution: &SudokuBoard) -> bool {
+        self.iter_filled()
+            .all(|(pos, space)| solution[pos] == Some(space))
+    }
+
+    /// Places `space` at `pos`, replacing any value already there, unless a
+    /// cell sharing its row, column or square already holds it.
+    pub fn set_cell(&mut self, pos: Pos, space: Space) -> Result<(), Conflict> {
+        if let Some(peer) = SudokuBoard::peers(pos).find(|&peer| self[peer] == Some(space)) {
+            return Err(Conflict { space, pos, peer });
+        }
+        self[pos] = Some(space);
+        Ok(())
+    }
+
+    /// Empties the cell at `pos`, returning the value it held.
+    pub fn clear_cell(&mut self, pos: Pos) -> Option<Space> {
+        self[pos].take()
+    }
+
+    /// Values the cell at `pos` could take, ruling out only those already
+    /// placed in its row, column or square. A filled cell gives just its value.
+    pub fn candidates_at(&self, pos: Pos) -> SudokuChoices {
+        if let Some(space) = self[pos] {
+            return SudokuChoices::one(space);
+        }
+        let mut candidates = SudokuChoices::all();
+        for &other in Rules::classic().peers(pos.into()) {
+            if let Some(space) = self[other] {
+                candidates.set(space, false);
+            }
+        }
+        candidates
+    }
+
+    /// Searches for a completed board, returning `None` if there is no solution.
+    pub fn solve(&self) -> Option<SudokuBoard> {
+        Solver::new().solve(self)
+    }
+
+    /// Plain depth-first backtracking: fills the first empty cell with each
+    /// value its peers allow in turn, with no reduction and no heuristics.
+    /// Only practical on easy boards, as a baseline for [`SudokuBoard::solve`].
+    pub fn solve_naive(&self) -> Option<SudokuBoard> {
+        fn backtrack(board: &mut SudokuBoard) -> bool {
+            let Some(pos) = Pos::all().find(|&pos| board[pos].is_none()) else {
+                return true;
+            };
+            for space in Space::all() {
+                if board.set_cell(pos, space).is_ok() && backtrack(board) {
+                    return true;
+                }
+            }
+            board.clear_cell(pos);
+            false
+        }
+
+        self.validate().ok()?;
+        let mut board = self.clone();
+        backtrack(&mut board).then_some(board)
+    }
+
+    /// Like [`SudokuBoard::solve`], but gives up and returns `None` once
+    /// `timeout` has passed.
+    pub fn solve_with_timeout(&self, timeout: Duration) -> Option<SudokuBoard> {
+        Solver::new().with_timeout(timeout).solve(self)
+    }
+
+    /// Like [`SudokuBoard::solve`], also reporting how the search went.
+    pub fn solve_with_stats(&self) -> (Option<SudokuBoard>, SearchStats) {
+        Solver::new().solve_with_stats(self)
+    }
+
+    /// Like [`SudokuBoard::solve`], showing `on_state` every board the search
+    /// passes through on the way, as by [`Solver::on_state`].
+    pub fn solve_observed(&self, on_state: impl FnMut(&SudokuBoard)) -> Option<SudokuBoard> {
+        Solver::new().on_state(on_state).solve(self)
+    }
+
+    /// Like [`SudokuBoard::solve`], but gives up once the search has expanded
+    /// `max_nodes` nodes, bounding the work an adversarial board can cause.
+    pub fn solve_within_nodes(
+        &self,
+        max_nodes: usize,
+    ) -> Result<Option<SudokuBoard>, NodeLimitExceeded> {
+        let (solution, stats) = Solver::new()
+            .with_node_limit(max_nodes)
+            .solve_with_stats(self);
+        match solution {
+            None if stats.nodes_expanded > max_nodes => Err(NodeLimitExceeded { max_nodes }),
+            solution => Ok(solution),
+        }
+    }
+
+    /// Solves each puzzle in `input`, one 81-character board per line as in
+    /// the common benchmark files. Blank lines are skipped, and reading stops
+    /// at the first I/O error. A puzzle with no solution yields `Ok(None)`.
+    pub fn solve_many(
+        input: impl BufRead,
+    ) -> impl Iterator<Item = Result<Option<SudokuBoard>, ParseBoardError>> {
+        input
+            .lines()
+            .map_while(Result::ok)
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| Ok(line.trim().parse::<SudokuBoard>()?.solve()))
+    }
+
+    /// Solves the board, telling apart puzzles with no solution and puzzles
+    /// with more than one.
+    pub fn solve_checked(&self) -> SolveResult {
+        let mut solutions = self.solutions();
+        match (solutions.next(), solutions.next()) {
+            (None, _) => SolveResult::NoSolution,
+            (Some(solution), None) => SolveResult::Unique(solution),
+            (Some(solution), Some(_)) => SolveResult::Multiple(solution),
+        }
+    }
+
+    /// Whether the board can still be completed. Unlike
+    /// [`SudokuBoard::validate`], this also catches boards that break no rule
+    /// yet but have left some cell or region without a way forward.
+    ///
+    /// Reduction settles most boards; the rest take a search that stops at
+    /// the first solution.
+    pub fn is_solvable(&self) -> bool {
+        if self.validate().is_err() {
+            return false;
+        }
+        let mut board = self.clone();
+        let (_, is_invalid) = board.reduce();
+        !is_invalid && board.solve().is_some()
+    }
+
+    /// Why the board has no solution, or `None` if it has one.
+    ///
+    /// Givens that already clash are reported as by [`SudokuBoard::validate`].
+    /// Otherwise the first empty cell left without candidates, or value left
+    /// without a place in some region, is described, looking first at the
+    /// givens alone and then after reduction.
+    pub fn unsolvable_reason(&self) -> Option<String> {
+        let contradiction = |possibilities_board: &PossibilitySpaceBoard| {
+            let empty_cell = self
+                .iter_empty()
+                .find(|&pos| possibilities_board[pos].is_empty());
+            if let Some((x, y)) = empty_cell {
+                return Some(format!("Cell ({x}, {y}) has no remaining candidates"));
+            }
+            Rules::classic().houses().find_map(|(region, cells)| {
+                let space = Space::all()
+                    .into_iter()
+                    .find(|&space| cells.iter().all(|&pos| !possibilities_board[pos][space]))?;
+                let region = match region {
+                    Row(i) => format!("row {i}"),
+                    Column(i) => format!("column {i}"),
+                    Square(i) => format!("square {i}"),
+                    Diagonal(i) => format!("diagonal {i}"),
+                    Window(i) => format!("window {i}"),
+                };
+                Some(format!(
+                    "Value {} can't be placed anywhere in {region}",
+                    usize::from(space)
+                ))
+            })
+        };
+
+        if let Err(reason) = self.validate() {
+            return Some(reason);
+        }
+        if let Some(reason) = contradiction(&self.candidates()) {
+            return Some(reason);
+        }
+        let mut board = self.clone();
+        let (possibilities_board, is_invalid) = board.reduce();
+        if is_invalid {
+            return Some(contradiction(&possibilities_board).unwrap_or_else(|| {
+                String::from("Filling in the cells that follow logically leads to a clash")
+            }));
+        }
+        match board.solve() {
+            Some(_) => None,
+            None => Some(String::from(
+                "Every way of filling in the remaining cells leads to a contradiction",
+            )),
+        }
+    }
+
+    /// Fills an empty board with a random valid solution drawn from `rng`.
+    pub fn generate_full(rng: &mut impl Rng) -> SudokuBoard {
+        Solver::new()
+            .shuffled(rng)
+            .solve(&SudokuBoard::empty())
+            .expect("An empty board always has a solution")
+    }
+
+    /// Generates a uniquely solvable puzzle by removing clues from a random
+    /// full grid until no more can be removed or only `min_clues` remain.
+    pub fn generate_puzzle(rng: &mut impl Rng, min_clues: usize) -> SudokuBoard {
+        let mut board = SudokuBoard::generate_full(rng);
+        let mut positions = SudokuBoard::iter_positions().collect::<Vec<_>>();
+        positions.shuffle(rng);
+        let mut clues = CELLS;
+        for pos in positions {
+            if clues <= min_clues {
+                break;
+            }
+            let space = board[pos].take();
+            if board.has_unique_solution() {
+                clues -= 1;
+            } else {
+                board[pos] = space;
+            }
+        }
+        board
+    }
+
+    /// Like [`SudokuBoard::generate_puzzle`], but removes clues together with
+    /// their images under `symmetry`, so the clues that remain follow it.
+    pub fn generate_symmetric(rng: &mut impl Rng, symmetry: Symmetry) -> SudokuBoard {
+        let mut board = SudokuBoard::generate_full(rng);
+        let mut positions = SudokuBoard::iter_positions().collect::<Vec<_>>();
+        positions.shuffle(rng);
+        for pos in positions {
+            if board[pos].is_none() {
+                continue;
+            }
+            let orbit = symmetry.orbit(pos);
+            let spaces = orbit
+                .iter()
+                .map(|&pos| board[pos].take())
+                .collect::<Vec<_>>();
+            if !board.has_unique_solution() {
+                for (&pos, space) in orbit.iter().zip(spaces) {
+                    board[pos] = space;
+                }
+            }
+        }
+        board
+    }
+
+    /// Generates puzzles until one rates as `target`. Every difficulty turns
+    /// up often enough that this takes a handful of tries, but after
+    /// [`GENERATE_ATTEMPTS`] the puzzle rated closest to `target` is returned
+    /// rather than searching forever.
+    pub fn generate_rated(rng: &mut impl Rng, target: Difficulty) -> SudokuBoard {
+        let mut closest: Option<(usize, SudokuBoard)> = None;
+        for _ in 0..GENERATE_ATTEMPTS {
+            let board = SudokuBoard::generate_puzzle(rng, 0);
+            let distance = (board.difficulty() as usize).abs_diff(target as usize);
+            if distance == 0 {
+                return board;
+            }
+            if closest.as_ref().is_none_or(|(best, _)| distance < *best) {
+                closest = Some((distance, board));
+            }
+        }
+        closest.expect("at least one puzzle is generated").1
+    }
+
+    /// The puzzle of the day for `date` at `difficulty`, the same for
+    /// everyone using this version of the crate.
+    #[cfg(feature = "chrono")]
+    pub fn daily(date: NaiveDate, difficulty: Difficulty) -> SudokuBoard {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let seed = (date.num_days_from_ce() as u64) << 8 | difficulty as u64;
+        SudokuBoard::generate_rated(&mut StdRng::seed_from_u64(seed), difficulty)
+    }
+
+    /// Removes clues in reading order for as long as the puzzle keeps a
+    /// unique solution, leaving a minimal puzzle: taking away any remaining
+    /// clue would allow more than one solution. A board without a unique
+    /// solution is returned unchanged.
+    pub fn minimize(&self) -> SudokuBoard {
+        let mut board = self.clone();
+        if !board.has_unique_solution() {
+            return board;
+        }
+        // a clue needed now stays needed as others go, so one pass is enough
+        for pos in SudokuBoard::iter_positions() {
+            let Some(space) = board[pos].take() else {
+                continue;
+            };
+            if !board.has_unique_solution() {
+                board[pos] = Some(space);
+            }
+        }
+        board
+    }
+
+    /// Fills `n` random empty cells with their values from the solution, for
+    /// a player who's stuck and asks for help. Every empty cell is filled if
+    /// there are fewer than `n`. A board without a unique solution is
+    /// returned unchanged, as there's no one answer to give away.
+    pub fn reveal_hints(&self, n: usize, rng: &mut impl Rng) -> SudokuBoard {
+        let mut board = self.clone();
+        let SolveResult::Unique(solution) = self.solve_checked() else {
+            return board;
+        };
+        let mut positions = self.iter_empty().collect::<Vec<_>>();
+        positions.shuffle(rng);
+        for pos in positions.into_iter().take(n) {
+            board[pos] = solution[pos];
+        }
+        board
+    }
+
+    /// Lazily yields every completion of this board.
+    pub fn solutions(&self) -> impl Iterator<Item = SudokuBoard> {
+        Solver::new().solutions(self)
+    }
+
+    /// Counts the distinct solutions of this board, stopping once `limit` are found.
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        Solver::new().count_solutions(self, limit)
+    }
+
+    /// Whether exactly one completion of this board exists.
+    pub fn has_unique_solution(&self) -> bool {
+        self.count_solutions(2) == 1
+    }
+
+    /// Every filled cell with its value, in reading order.
+    pub fn iter_filled(&self) -> impl Iterator<Item = (BoardPosition, Space)> + '_ {
+        SudokuBoard::iter_positions()
+            .zip(self.iter())
+            .filter_map(|(pos, space)| Some((pos, (*space)?)))
+    }
+
+    /// Every empty cell, in reading order.
+    pub fn iter_empty(&self) -> impl Iterator<Item = BoardPosition> + '_ {
+        SudokuBoard::iter_positions()
+            .zip(self.iter())
+            .filter(|(_, space)| space.is_none())
+            .map(|(pos, _)| pos)
+    }
+
+    /// Number of filled cells.
+    pub fn clue_count(&self) -> usize {
+        CELLS - self.empty_count()
+    }
+
+    /// Number of empty cells.
+    pub fn empty_count(&self) -> usize {
+        self.iter().filter(|space| space.is_none()).count()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseBoardError {
+    /// A character that isn't a digit or an empty-cell marker. `col` counts
+    /// characters rather than bytes, so it is the grid column even for
+    /// multi-byte input.
+    InvalidChar {
+        ch: char,
+        row: usize,
+        col: usize,
+    },
+    /// A cell between separators holding more than one character, such as
+    /// `10`.
+    InvalidCell {
+        cell: String,
+        row: usize,
+        col: usize,
+    },
+    WrongCellCount {
+        found: usize,
+    },
+    /// A line of a multi-line board that doesn't hold exactly one row.
+    WrongRowLength {
+        row: usize,
+        found: usize,
+    },
+    /// A blank line in a multi-line board, usually from a trailing newline.
+    EmptyRow {
+        row: usize,
+    },
+    /// Two givens share a row, column or square and hold the same value.
+    ConflictingGivens {
+        space: Space,
+        first: Pos,
+        second: Pos,
+    },
+}
+
+impl Display for ParseBoardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseBoardError::InvalidChar { ch, row, col } => write!(
+                f,
+                "Character '{ch}' at row {row}, column {col} is not valid for a sudoku board"
+            ),
+            ParseBoardError::InvalidCell { cell, row, col } => write!(
+                f,
+                "Cell \"{cell}\" at row {row}, column {col} is not a single value"
+            ),
+            ParseBoardError::WrongCellCount { found } => write!(
+                f,
+                "Incorrect number of spaces on sudoku board: expected {CELLS}, found {found}"
+            ),
+            ParseBoardError::WrongRowLength { row, found } => {
+                write!(f, "Row {row} has {found} cells, expected {SIDE}")
+            }
+            ParseBoardError::EmptyRow { row } => write!(
+                f,
+                "Row {row} is empty; remove blank lines and trailing newlines"
+            ),
+            ParseBoardError::ConflictingGivens {
+                space,
+                first,
+                second,
+            } => write!(
+                f,
+                "Givens at {first} and {second} are both {space:?} in the same region"
+            ),
+        }
+    }
+}
+
+impl Error for ParseBoardError {}
+
+/// Why [`SudokuBoard::set_cell`] refused a placement.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Conflict {
+    pub space: Space,
+    pub pos: Pos,
+    /// The cell already holding `space` in the same region.
+    pub peer: Pos,
+}
+
+impl Display for Conflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Conflict { space, pos, peer } = self;
+        write!(f, "Can't place {space:?} at {pos}: {peer} already holds it")
+    }
+}
+
+impl Error for Conflict {}
+
+impl FromStr for SudokuBoard {
+    type Err = ParseBoardError;
+
+    /// Reads either all 81 cells on one line or one row per line. Cells may
+    /// be separated by commas, tabs, or a single space between every cell,
+    /// as pasted from a spreadsheet; an empty cell between separators is
+    /// blank.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // a lone line followed by newlines still counts as one line
+        let multi_line = s.split('\n').filter(|line| !line.is_empty()).count() > 1;
+        let separator = [',', '\t'].into_iter().find(|&sep| s.contains(sep));
+        // spaces can also mark blanks, so only a space after every cell counts
+        let spaced = separator.is_none()
+            && s.split('\n').filter(|line| !line.is_empty()).all(|line| {
+                let len = line.chars().count();
+                (len == 2 * SIDE - 1 || len == 2 * CELLS - 1)
+                    && line.chars().skip(1).step_by(2).all(|ch| ch == ' ')
+            });
+        let lines: Vec<Vec<char>> = match separator {
+            Some(separator) => s
+                .split('\n')
+                .enumerate()
+                .map(|(row, line)| {
+                    line.split(separator)
+                        .enumerate()
+                        .map(|(col, cell)| {
+                            let cell = cell.trim();
+                            let mut chars = cell.chars();
+                            match (chars.next(), chars.next()) {
+                                (None, _) => Ok('.'),
+                                (Some(ch), None) => Ok(ch),
+                                (Some(_), Some(_)) => Err(ParseBoardError::InvalidCell {
+                                    cell: cell.to_string(),
+                                    row,
+                                    col,
+                                }),
+                            }
+                        })
+                        .collect()
+                })
+                .collect::<Result<_, _>>()?,
+            None if spaced => s
+                .split('\n')
+                .map(|line| line.chars().step_by(2).collect())
+                .collect(),
+            None => s.split('\n').map(|line| line.chars().collect()).collect(),
+        };
+        let rows = if !multi_line && lines[0].len() == CELLS {
+            // single line, row-major format
+            lines[0].chunks(SIDE).map(<[char]>::to_vec).collect()
+        } else {
+            lines
+        };
+        let collect = rows
+            .into_iter()
+            .enumerate()
+            .map(|(row, chars)| {
+                let cells = chars
+                    .into_iter()
+                    .enumerate()
+                    .map(|(col, ch)| match ch {
+                        ' ' | '.' | '0' => Ok(None),
+                        '1'..='9' => Ok(Some(
+                            Space::try_from(
+                                ch.to_digit(10)
+                                    .expect("char will always be convertible to a digit")
+                                    as usize,
+                            )
+                            .expect("char will always be convertible to a digit"),
+                        )),
+                        _ => Err(ParseBoardError::InvalidChar { ch, row, col }),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                match cells.len() {
+                    _ if !multi_line => Ok(cells),
+                    SIDE => Ok(cells),
+                    0 => Err(ParseBoardError::EmptyRow { row }),
+                    found => Err(ParseBoardError::WrongRowLength { row, found }),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        let found = collect.len();
+        Ok(Board(
+            collect
+                .try_into()
+                .map_err(|_| ParseBoardError::WrongCellCount { found })?,
+        ))
+    }
+}
+
+impl SudokuBoard {
+    /// Parses like [`FromStr`], but also rejects boards whose givens already
+    /// contradict each other.
+    pub fn from_str_validated(s: &str) -> Result<SudokuBoard, ParseBoardError> {
+        let board: SudokuBoard = s.parse()?;
+        match board.conflicts().first() {
+            Some(&(space, first, second)) => Err(ParseBoardError::ConflictingGivens {
+                space,
+                first,
+                second,
+            }),
+            None => Ok(board),
+        }
+    }
+}
+
+/// Rows from top to bottom, each holding its cells from left to right.
+impl From<[[Option<Space>; SIDE]; SIDE]> for SudokuBoard {
+    fn from(rows: [[Option<Space>; SIDE]; SIDE]) -> Self {
+        Board::from_fn(|(x, y)| rows[y][x])
+    }
+}
+
+impl SudokuBoard {
+    /// The board as rows from top to bottom, each holding its cells from left
+    /// to right, so `to_2d()[y][x]` is the cell at `(x, y)`.
+    pub fn to_2d(&self) -> [[Option<Space>; SIDE]; SIDE] {
+        array::from_fn(|y| array::from_fn(|x| self[(x, y)]))
+    }
+}
+
+impl Display for SudokuBoard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.0
+                .chunks_exact(SIDE)
+                .map(|row| {
+                    row.iter()
+                        .map(|space| match space {
+                            None => String::from(" "),
+                            Some(space) => format!("{}", usize::from(*space)),
+                        })
+                        .collect::<Vec<_>>()
+                        .join("")
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        )
+    }
+}
+
+impl SudokuBoard {
+    /// Renders the board with box-drawing borders around each box, and empty
+    /// cells shown as dots.
+    pub fn to_grid_string(&self) -> String {
+        let border = |left: &str, middle: &str, right: &str| {
+            let segment = "─".repeat(BOX_SIZE);
+            format!("{left}{}{right}", vec![segment; BOX_SIZE].join(middle))
+        };
+        let mut lines = vec![border("┌", "┬", "┐")];
+        for (y, row) in self.0.chunks_exact(SIDE).enumerate() {
+            if y > 0 && y % BOX_SIZE == 0 {
+                lines.push(border("├", "┼", "┤"));
+            }
+            let boxes = row
+                .chunks_exact(BOX_SIZE)
+                .map(|cells| {
+                    cells
+                        .iter()
+                        .map(|space| match space {
+                            None => String::from("."),
+                            Some(space) => format!("{}", usize::from(*space)),
+                        })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>();
+            lines.push(format!("│{}│", boxes.join("│")));
+        }
+        lines.push(border("└", "┴", "┘"));
+        lines.join("\n")
+    }
+
+    /// Renders the board like its [`Display`] output, with the clues from
+    /// `givens` in bold blue and every other filled cell in green.
+    ///
+    /// Colour is left out when stdout isn't a terminal or `NO_COLOR` is set,
+    /// so redirected output stays plain. Use
+    /// [`SudokuBoard::display_colored_with`] to choose explicitly.
+    pub fn display_colored(&self, givens: &SudokuBoard) -> String {
+        let color = io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+        self.display_colored_with(givens, color)
+    }
+
+    /// Like [`SudokuBoard::display_colored`], with ANSI colour codes emitted
+    /// only if `color` is set.
+    pub fn display_colored_with(&self, givens: &SudokuBoard, color: bool) -> String {
+        const GIVEN: &str = "\x1b[1;34m";
+        const SOLVED: &str = "\x1b[32m";
+        const RESET: &str = "\x1b[0m";
+        self.0
+            .chunks_exact(SIDE)
+            .zip(givens.0.chunks_exact(SIDE))
+            .map(|(row, given_row)| {
+                row.iter()
+                    .zip(given_row)
+                    .map(|(space, given)| match space {
+                        None => String::from(" "),
+                        Some(space) if !color => format!("{}", usize::from(*space)),
+                        Some(space) => {
+                            let style = if given.is_some() { GIVEN } else { SOLVED };
+                            format!("{style}{}{RESET}", usize::from(*space))
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+pub(crate) enum NextBoardStates<I> {
+    Single(Option<SudokuBoard>),
+    States(I),
+}
+
+impl<I> Iterator for NextBoardStates<I>
+where
+    I: Iterator<Item = SudokuBoard>,
+{
+    type Item = SudokuBoard;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            NextBoardStates::Single(board) => board.take(),
+            NextBoardStates::States(iter) => iter.next(),
+        }
+    }
+}
+
+impl Searchable for SudokuBoard {
+    fn next_states(&self) -> impl Iterator<Item = Self> {
+        self.next_states_with(
+            Rules::classic(),
+            &techniques::sound(),
+            ValueOrder::Ascending,
+        )
+    }
+}
+
+impl SudokuBoard {
+    /// Reduces the board under `rules` with `techniques`, then branches on the
+    /// empty cell with the fewest candidates if it is still unfinished, trying
+    /// its values in `order`.
+    pub(crate) fn next_states_with(
+        &self,
+        rules: &Rules,
+        techniques: &[Technique],
+        order: ValueOrder,
+    ) -> NextBoardStates<std::vec::IntoIter<SudokuBoard>> {
+        let mut reduced_board = self.clone();
+        let (possibilities_board, is_invalid) = reduced_board.reduce_with(rules, techniques);
+        if is_invalid {
+            NextBoardStates::Single(None)
+        } else if reduced_board.is_complete() || &reduced_board != self {
+            NextBoardStates::Single(Some(reduced_board))
+        } else {
+            // every empty cell must take one of its candidates, so branching on
+            // a single cell is enough to cover the whole search space; pick the
+            // one with the fewest candidates to keep the tree narrow
+            let branch = reduced_board
+                .iter_empty()
+                .min_by_key(|&pos| possibilities_board[pos].len());
+            let branches = match branch {
+                None => Vec::new(),
+                Some(pos) => {
+                    let mut spaces = possibilities_board[pos].iter().collect::<Vec<_>>();
+                    if order == ValueOrder::LeastConstraining {
+                        // the sort is stable, so ties still go from 1 up
+                        spaces.sort_by_key(|&space| {
+                            rules
+                                .peers(pos)
+                                .iter()
+                                .filter(|&&other| {
+                                    reduced_board[other].is_none()
+                                        && possibilities_board[other][space]
+                                })
+                                .count()
+                        });
+                    }
+                    spaces
+                        .into_iter()
+                        .map(|space| {
+                            let mut new_board = reduced_board.clone();
+                            new_board[pos] = Some(space);
+                            new_board
+                        })
+                        .collect()
+                }
+            };
+            NextBoardStates::States(branches.into_iter())
+        }
+    }
+}
+
+impl SudokuBoard {
+    /// Whether every cell is filled, without checking that the values are
+    /// consistent. Cheap enough for the reduction and search loops, which only
+    /// ever place consistent values; use [`SolutionIdentifiable::is_solution`]
+    /// for boards from elsewhere.
+    pub(crate) fn is_complete(&self) -> bool {
+        self.iter().all(|space| space.is_some())
+    }
+}
+
+impl SolutionIdentifiable for SudokuBoard {
+    /// Whether every cell is filled and no row, column or square repeats a value.
+    fn is_solution(&self) -> bool {
+        Rules::classic().is_solution(self)
+    }
+}
+
+impl Scoreable for SudokuBoard {
+    type Score = usize;
+
+    fn score(&self) -> Self::Score {
+        self.empty_count()
+    }
+}
+
+/// The puzzle most tests start from.
+#[cfg(test)]
+pub(crate) fn easy_puzzle() -> SudokuBoard {
+    "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79"
+        .parse()
+        .unwrap()
+}
+
+/// The only solution of [`easy_puzzle`].
+#[cfg(test)]
+pub(crate) fn easy_solution() -> SudokuBoard {
+    "534678912672195348198342567859761423426853791713924856961537284287419635345286179"
+        .parse()
+        .unwrap()
+}
+
+/// A puzzle that logic alone doesn't finish, for tests that need candidates
+/// left over.
+#[cfg(test)]
+pub(crate) fn stalled_puzzle() -> SudokuBoard {
+    ".............6..47..94......6.7.5..3.971....8..1...5....2...8.....92.....8...13.9"
+        .parse()
+        .unwrap()
+}
+
+#[test]
+fn test_reduction() {
+    #[rustfmt::skip]
+    let board_str = 
+"53  7    
+6  195   
+ 98    6 
+8   6   3
+4  8 3  1
+7   2   6
+ 6    28 
+   419  5
+    8  79";
+    let mut board: SudokuBoard = board_str.parse().unwrap();
+    println!("initial board:");
+    println!("{}", board);
+    board.reduce();
+    println!("after reduction:");
+    println!("{}", board);
+    assert_eq!(board.validate(), Ok(()));
+}
+
+#[test]
+fn test_pencil_marks() {
+    let board = stalled_puzzle();
+    let solution = board.solve().expect("Sudoku board has a solution");
+    let pencil_marks = board.pencil_marks();
+    assert!(SudokuBoard::iter_positions().all(|pos| {
+        let space = solution[pos].expect("solutions have every cell filled");
+        pencil_marks[pos][space]
+    }));
+    assert!(SudokuBoard::iter_positions()
+        .filter_map(|pos| board[pos].map(|space| (pos, space)))
+        .all(|(pos, space)| pencil_marks[pos] == SudokuChoices::one(space)));
+    // logic alone doesn't finish this board
+    assert!(pencil_marks.iter().any(|choices| choices.len() > 1));
+    assert_eq!(pencil_marks.to_string().lines().count(), SIDE);
+
+    assert!(solution
+        .pencil_marks()
+        .iter()
+        .all(|choices| choices.len() == 1));
+}
+
+#[test]
+fn test_autofill() {
+    let puzzle = stalled_puzzle();
+    let solution = puzzle.solve().expect("Sudoku board has a solution");
+    let mut board = puzzle.clone();
+    let filled = board.autofill();
+    assert!(filled > 0);
+    assert_eq!(board.clue_count(), puzzle.clue_count() + filled);
+    // logic alone doesn't finish this board
+    assert!(board.empty_count() > 0);
+    for (pos, space) in board.iter_filled() {
+        assert_eq!(solution[pos], Some(space));
+    }
+    for (pos, space) in puzzle.iter_filled() {
+        assert_eq!(board[pos], Some(space));
+    }
+    assert_eq!(board.clone().autofill(), 0);
+
+    let mut contradictory = puzzle.clone();
+    contradictory[(0, 0)] = Some(Space::Nine);
+    contradictory[(1, 0)] = Some(Space::Nine);
+    let before = contradictory.clone();
+    assert_eq!(contradictory.autofill(), 0);
+    assert_eq!(contradictory, before);
+}
+
+#[test]
+fn test_is_consistent_with() {
+    let puzzle = easy_puzzle();
+    let solution = puzzle.solve().expect("Sudoku board has a solution");
+    assert!(puzzle.is_consistent_with(&solution));
+    assert!(SudokuBoard::empty().is_consistent_with(&solution));
+    assert!(solution.is_consistent_with(&solution));
+
+    let mut progress = puzzle.clone();
+    progress[(2, 0)] = solution[(2, 0)];
+    progress[(8, 8)] = solution[(8, 8)];
+    assert!(progress.is_consistent_with(&solution));
+
+    let wrong = Space::all()
+        .into_iter()
+        .find(|&space| solution[(3, 0)] != Some(space));
+    progress[(3, 0)] = wrong;
+    assert!(!progress.is_consistent_with(&solution));
+    assert!(!solution.is_consistent_with(&progress));
+}
+
+#[test]
+fn test_reveal_hints() {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(3);
+    let puzzle = stalled_puzzle();
+    let solution = puzzle.solve().expect("Sudoku board has a solution");
+    let revealed = puzzle.reveal_hints(5, &mut rng);
+    assert_eq!(revealed.clue_count(), puzzle.clue_count() + 5);
+    assert!(revealed.has_unique_solution());
+    for (pos, space) in revealed.iter_filled() {
+        assert_eq!(solution[pos], Some(space));
+    }
+    for (pos, space) in puzzle.iter_filled() {
+        assert_eq!(revealed[pos], Some(space));
+    }
+    assert_eq!(puzzle.reveal_hints(CELLS, &mut rng), solution);
+
+    assert_eq!(
+        SudokuBoard::empty().reveal_hints(5, &mut rng),
+        SudokuBoard::empty()
+    );
+}
+
+#[test]
+fn test_propagate() {
+    let easy = easy_puzzle();
+    let mut board = easy.clone();
+    assert_eq!(board.propagate(), PropagationOutcome::Solved);
+    assert_eq!(Some(board), easy.solve());
+
+    let hard = stalled_puzzle();
+    let mut board = hard.clone();
+    assert_eq!(board.propagate(), PropagationOutcome::Stuck);
+    let mut autofilled = hard.clone();
+    autofilled.autofill();
+    assert_eq!(board, autofilled);
+
+    let mut contradictory = hard.clone();
+    contradictory[(0, 0)] = Some(Space::Nine);
+    contradictory[(1, 0)] = Some(Space::Nine);
+    let before = contradictory.clone();
+    assert_eq!(contradictory.propagate(), PropagationOutcome::Contradiction);
+    assert_eq!(contradictory, before);
+}
+
+#[test]
+fn test_candidate_counts() {
+    let board = stalled_puzzle();
+    let counts = board.candidate_counts();
+    let pencil_marks = board.pencil_marks();
+    for (x, y) in SudokuBoard::iter_positions() {
+        assert_eq!(usize::from(counts[y][x]), pencil_marks[(x, y)].len());
+    }
+    for ((x, y), _) in board.iter_filled() {
+        assert_eq!(counts[y][x], 1);
+    }
+
+    let most_constrained = SudokuBoard::iter_positions()
+        .filter(|&(x, y)| counts[y][x] > 1)
+        .min_by_key(|&(x, y)| counts[y][x])
+        .expect("logic alone doesn't finish this board");
+    let fewest = pencil_marks
+        .iter()
+        .map(|choices| choices.len())
+        .filter(|&len| len > 1)
+        .min();
+    assert_eq!(pencil_marks[most_constrained].len(), 2);
+    assert_eq!(fewest, Some(2));
+
+    assert_eq!(
+        SudokuBoard::empty().candidate_counts(),
+        [[SIDE as u8; SIDE]; SIDE]
+    );
+}
+
+#[test]
+fn test_reduce_contradiction() {
+    use std::time::Instant;
+
+    // two 5s in the top row
+    let mut board: SudokuBoard =
+        "53..7...56..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79"
+            .parse()
+            .unwrap();
+    let start = Instant::now();
+    let (_, is_invalid) = board.reduce();
+    assert!(is_invalid);
+    assert!(start.elapsed() < Duration::from_secs(1));
+
+    // nothing is left for the top left corner, with a 9 below it
+    let mut board: SudokuBoard =
+        ".123456789......................................................................."
+            .parse()
+            .unwrap();
+    let (_, is_invalid) = board.reduce();
+    assert!(is_invalid);
+
+    // a board that runs out of passes before settling counts as invalid too;
+    // this one settles on its eighth
+    let hard: SudokuBoard =
+        "..65.....7.5..23...3.....8..5..96.7.1.4.....8...82.....2.....9...72..4.......75.."
+            .parse()
+            .unwrap();
+    let techniques = techniques::sound();
+    let (_, is_invalid) = hard.clone().reduce_within(Rules::classic(), &techniques, 7);
+    assert!(is_invalid);
+    let (_, is_invalid) = hard.clone().reduce_within(Rules::classic(), &techniques, 8);
+    assert!(!is_invalid);
+}
+
+#[test]
+fn test_parse_single_line() {
+    #[rustfmt::skip]
+    let board_str = 
+"53  7    
+6  195   
+ 98    6 
+8   6   3
+4  8 3  1
+7   2   6
+ 6    28 
+   419  5
+    8  79";
+    let board: SudokuBoard = board_str.parse().unwrap();
+    let single_line_board: SudokuBoard = board_str.replace('\n', "").parse().unwrap();
+    assert_eq!(board, single_line_board);
+}
+
+#[test]
+fn test_parse_empty_markers() {
+    #[rustfmt::skip]
+    let board_str = 
+"53  7    
+6  195   
+ 98    6 
+8   6   3
+4  8 3  1
+7   2   6
+ 6    28 
+   419  5
+    8  79";
+    let board: SudokuBoard = board_str.parse().unwrap();
+    let dot_board: SudokuBoard = board_str.replace(' ', ".").parse().unwrap();
+    let zero_board: SudokuBoard = board_str.replace(' ', "0").parse().unwrap();
+    assert_eq!(board, dot_board);
+    assert_eq!(board, zero_board);
+    let line_board = easy_puzzle();
+    assert_eq!(board, line_board);
+}
+
+#[test]
+fn test_parse_errors() {
+    let invalid_char =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..7x"
+            .parse::<SudokuBoard>();
+    assert_eq!(
+        invalid_char,
+        Err(ParseBoardError::InvalidChar {
+            ch: 'x',
+            row: 8,
+            col: 8
+        })
+    );
+    let too_short = "53..7....6..195....98".parse::<SudokuBoard>();
+    assert_eq!(
+        too_short,
+        Err(ParseBoardError::WrongCellCount { found: 21 })
+    );
+}
+
+#[test]
+fn test_from_str_validated() {
+    let board_str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+    assert_eq!(
+        SudokuBoard::from_str_validated(board_str),
+        board_str.parse::<SudokuBoard>()
+    );
+    let duplicate_five = board_str.replacen('.', "5", 1);
+    assert!(duplicate_five.parse::<SudokuBoard>().is_ok());
+    assert_eq!(
+        SudokuBoard::from_str_validated(&duplicate_five),
+        Err(ParseBoardError::ConflictingGivens {
+            space: Space::Five,
+            first: Pos::new(0, 0).unwrap(),
+            second: Pos::new(2, 0).unwrap(),
+        })
+    );
+}
+
+#[test]
+fn test_parse_ragged_rows() {
+    let short_row = "53..7....\n6..195...\n.98....6.\n8...6..3\n4..8.3..1\n7...2...6\n.6....28.\n...419..5\n....8..79"
+        .parse::<SudokuBoard>();
+    assert_eq!(
+        short_row,
+        Err(ParseBoardError::WrongRowLength { row: 3, found: 8 })
+    );
+    assert_eq!(
+        short_row.unwrap_err().to_string(),
+        "Row 3 has 8 cells, expected 9"
+    );
+    let trailing_newline = "53..7....\n6..195...\n.98....6.\n8...6...3\n4..8.3..1\n7...2...6\n.6....28.\n...419..5\n....8..79\n"
+        .parse::<SudokuBoard>();
+    assert_eq!(trailing_newline, Err(ParseBoardError::EmptyRow { row: 9 }));
+
+    let single_line =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+    assert_eq!(
+        format!("{single_line}\n").parse::<SudokuBoard>(),
+        single_line.parse()
+    );
+    assert!(single_line.parse::<SudokuBoard>().is_ok());
+}
+
+#[test]
+fn test_parse_separated() {
+    let board = easy_puzzle();
+    let grid = board.to_string().replace(' ', ".");
+    let separated = |separator: &str| {
+        grid.lines()
+            .map(|row| {
+                row.chars()
+                    .map(String::from)
+                    .collect::<Vec<_>>()
+                    .join(separator)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let comma_rows = separated(",");
+    assert_eq!(comma_rows[0], "5,3,.,.,7,.,.,.,.");
+    assert_eq!(comma_rows.join("\n").parse(), Ok(board.clone()));
+    assert_eq!(comma_rows.join(",").parse(), Ok(board.clone()));
+    // spreadsheets leave blank cells empty
+    assert_eq!(
+        comma_rows.join("\n").replace('.', "").parse(),
+        Ok(board.clone())
+    );
+    assert_eq!(separated("\t").join("\n").parse(), Ok(board.clone()));
+
+    let space_rows = separated(" ");
+    assert_eq!(space_rows[0], "5 3 . . 7 . . . .");
+    assert_eq!(space_rows.join("\n").parse(), Ok(board.clone()));
+    assert_eq!(space_rows.join(" ").parse(), Ok(board.clone()));
+
+    let too_long = "5,3,.,.,7,.,.,.,10".parse::<SudokuBoard>();
+    assert_eq!(
+        too_long,
+        Err(ParseBoardError::InvalidCell {
+            cell: String::from("10"),
+            row: 0,
+            col: 8
+        })
+    );
+    assert_eq!(
+        too_long.unwrap_err().to_string(),
+        "Cell \"10\" at row 0, column 8 is not a single value"
+    );
+}
+
+#[test]
+fn test_parse_non_ascii() {
+    let accented =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..é9"
+            .parse::<SudokuBoard>();
+    assert_eq!(
+        accented,
+        Err(ParseBoardError::InvalidChar {
+            ch: 'é',
+            row: 8,
+            col: 7
+        })
+    );
+    let emoji = "53..7....\n6..19🦀...\n.98....6.\n8...6...3\n4..8.3..1\n7...2...6\n.6....28.\n...419..5\n....8..79"
+        .parse::<SudokuBoard>();
+    assert_eq!(
+        emoji,
+        Err(ParseBoardError::InvalidChar {
+            ch: '🦀',
+            row: 1,
+            col: 5
+        })
+    );
+}
+
+#[test]
+fn test_choices_bitmask() {
+    let mut choices = SudokuChoices::none();
+    choices.set(Space::Two, true);
+    choices.set(Space::Seven, true);
+    assert_eq!(choices.len(), 2);
+    assert_eq!(
+        choices.iter().collect::<Vec<_>>(),
+        [Space::Two, Space::Seven]
+    );
+    assert_eq!(choices.only(), None);
+    assert_eq!(
+        (choices & SudokuChoices::one(Space::Seven)).only(),
+        Some(Space::Seven)
+    );
+    assert_eq!(
+        (choices | SudokuChoices::one(Space::Nine)).to_string(),
+        "[ 2    7 9]"
+    );
+    assert!((choices & SudokuChoices::one(Space::One)).is_empty());
+    assert_eq!(SudokuChoices::all().len(), 9);
+}
+
+#[test]
+fn test_candidate_string() {
+    let board = stalled_puzzle();
+    let mut pencil_marks = board.pencil_marks();
+    pencil_marks[(8, 8)] = SudokuChoices::none();
+    let candidate_string = pencil_marks.to_candidate_string();
+    assert_eq!(candidate_string.split(' ').count(), CELLS);
+    assert!(candidate_string.ends_with(" -"));
+    assert_eq!(
+        PossibilitySpaceBoard::from_candidate_string(&candidate_string),
+        Ok(pencil_marks)
+    );
+
+    let rows = "123 4 56789 1 1 1 1 1 1\n".repeat(SIDE);
+    let parsed = PossibilitySpaceBoard::from_candidate_string(&rows).unwrap();
+    assert_eq!(
+        parsed[(0, 3)].iter().collect::<Vec<_>>(),
+        [Space::One, Space::Two, Space::Three]
+    );
+    assert_eq!(parsed[(1, 3)], SudokuChoices::one(Space::Four));
+    assert_eq!(parsed[(2, 8)].len(), 5);
+
+    assert_eq!(
+        PossibilitySpaceBoard::from_candidate_string("123 456"),
+        Err(ParseBoardError::WrongCellCount { found: 2 })
+    );
+    let zero = rows.replacen("56789", "5670", 1);
+    assert_eq!(
+        PossibilitySpaceBoard::from_candidate_string(&zero),
+        Err(ParseBoardError::InvalidChar {
+            ch: '0',
+            row: 0,
+            col: 2
+        })
+    );
+}
+
+#[test]
+fn test_solve_hard() {
+    #[rustfmt::skip]
+    let board_str = 
+"2  5 74 6
+    31   
+      23 
+    2    
+86 31    
+ 45      
+  9   7  
+  695   2
+  1  6  8";
+    let board: SudokuBoard = board_str.parse().unwrap();
+    println!("initial board:");
+    println!("{}", board);
+    let solution = board.solve().expect("Sudoku board has a solution");
+    println!("solution:");
+    println!("{}", solution);
+    assert_eq!(solution.validate(), Ok(()));
+}
+
+#[test]
+fn test_solve_hard_2() {
+    #[rustfmt::skip]
+    let board_str = 
+"  65     
+7 5  23  
+ 3     8 
+ 5  96 7 
+1 4     8
+   82    
+ 2     9 
+  72  4  
+     75  ";
+    let board: SudokuBoard = board_str.parse().unwrap();
+    println!("initial board:");
+    println!("{}", board);
+    let solution = board.solve().expect("Sudoku board has a solution");
+    println!("solution:");
+    println!("{}", solution);
+    assert_eq!(solution.validate(), Ok(()));
+}
+
+#[test]
+fn test_solve_hard_3() {
+    #[rustfmt::skip]
+    let board_str = 
+" 293 8456
+5782 61 9
+   1 5 7 
+3 5 2 6  
+     9 4 
+ 91 67   
+ 3  5    
+     29 3
+9 7    24";
+    let board: SudokuBoard = board_str.parse().unwrap();
+    println!("initial board:");
+    println!("{}", board);
+    let solution = board.solve().expect("Sudoku board has a solution");
+    println!("solution:");
+    println!("{}", solution);
+    assert_eq!(solution.validate(), Ok(()));
+}
+
+#[test]
+fn test_solve_hard_4() {
+    #[rustfmt::skip]
+    let board_str = 
+"5 8427   
+ 4  1 7  
+19   3  2
+    6   5
+7     2  
+6 513 9  
+9    15  
+    4  2 
+ 7      8";
+    let board: SudokuBoard = board_str.parse().unwrap();
+    println!("initial board:");
+    println!("{}", board);
+    let solution = board.solve().expect("Sudoku board has a solution");
+    println!("solution:");
+    println!("{}", solution);
+    assert_eq!(solution.validate(), Ok(()));
+}
+
+#[test]
+fn test_solo_candidate_deduction() {
+    #[rustfmt::skip]
+    let board_str = 
+"         
+3        
+6        
+2        
+1        
+     4   
+8        
+5        
+       4 ";
+    println!("{}", board_str.len());
+    let mut board: SudokuBoard = board_str.parse().unwrap();
+    println!("initial board:");
+    println!("{}", board);
+    board.reduce();
+    println!("solution:");
+    println!("{}", board);
+    assert_eq!(board.validate(), Ok(()));
+    assert_eq!(board[(0, 0)], Some(Space::Four));
+}
+
+#[test]
+fn test_reduction_2() {
+    let mut board: SudokuBoard = "2  5974 6
+6 4231   
+   8  23 
+    2    
+86231    
+ 45    2 
+4 918276 
+786953142
+ 21  6  8"
+        .parse()
+        .unwrap();
+    board.reduce();
+    let solution_board: SudokuBoard = "238597416
+694231857
+517864239
+173429685
+862315974
+945678321
+459182763
+786953142
+321746598"
+        .parse()
+        .unwrap();
+    assert_eq!(board, solution_board);
+}
+
+#[test]
+fn test_manual_solve() {
+    #[rustfmt::skip]
+    let board_str = 
+"2  5 74 6
+    31   
+      23 
+    2    
+86 31    
+ 45      
+  9   7  
+  695   2
+  1  6  8";
+    let mut board: SudokuBoard = board_str.parse().unwrap();
+    println!("\n{board}");
+    board.reduce();
+    println!("\n{board}");
+    println!("next moves: {}", board.next_states().count());
+    let before_adjustment = board.clone();
+    board[(3, 2)] = Some(Space::Eight);
+    println!("\n{board}");
+    assert!(before_adjustment
+        .next_states()
+        .find(|b| b == &board)
+        .is_some());
+    board.reduce();
+    println!("\n{board}");
+    assert_eq!(board.validate(), Ok(()));
+    assert!(board.is_solution());
+}
+
+#[test]
+fn test_solve_solved_board() {
+    let board: SudokuBoard = "238597416
+694231857
+517864239
+173429685
+862315974
+945678321
+459182763
+786953142
+321746598"
+        .parse()
+        .unwrap();
+    assert_eq!(board.solve(), Some(board));
+}
+
+#[test]
+fn test_solve_naive() {
+    let board = easy_puzzle();
+    let solution = board.solve_naive().expect("Sudoku board has a solution");
+    assert_eq!(Some(solution), board.solve());
+
+    let mut conflicting = board.clone();
+    conflicting[(2, 0)] = Some(Space::Five);
+    assert_eq!(conflicting.solve_naive(), None);
+}
+
+#[test]
+fn test_solve_many() {
+    let input = "\
+53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79
+..65.....7.5..23...3.....8..5..96.7.1.4.....8...82.....2.....9...72..4.......75..
+
+.293.84565782.61.9...1.5.7.3.5.2.6.......9.4..91.67....3..5.........29.39.7....24
+";
+    let solutions = SudokuBoard::solve_many(input.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(solutions.len(), 3);
+    for (line, solution) in input.lines().filter(|line| !line.is_empty()).zip(solutions) {
+        let board: SudokuBoard = line.parse().unwrap();
+        let solution = solution.expect("Sudoku board has a solution");
+        assert_eq!(solution.validate(), Ok(()));
+        assert!(board
+            .iter()
+            .zip(solution.iter())
+            .all(|(given, value)| given.is_none() || given == value));
+    }
+
+    let mut results = SudokuBoard::solve_many("123\n".as_bytes());
+    assert_eq!(
+        results.next(),
+        Some(Err(ParseBoardError::WrongCellCount { found: 3 }))
+    );
+    assert_eq!(results.next(), None);
+}
+
+#[test]
+fn test_solve_empty_board() {
+    let board: SudokuBoard = " ".repeat(81).parse().unwrap();
+    let solution = board.solve().expect("Empty board has a solution");
+    assert!(solution.is_solution());
+    assert_eq!(solution.validate(), Ok(()));
+}
+
+#[test]
+fn test_empty() {
+    let board = SudokuBoard::empty();
+    assert_eq!(board.clue_count(), 0);
+    assert_eq!(board, " ".repeat(81).parse().unwrap());
+    let solution = board.solve().expect("Empty board has a solution");
+    assert_eq!(solution.clue_count(), CELLS);
+    assert_eq!(solution.validate(), Ok(()));
+
+    let indices = Board::from_fn(|(x, y)| y * SIDE + x);
+    assert!(indices.iter().copied().eq(0..CELLS));
+}
+
+#[test]
+fn test_count_solutions() {
+    let board = easy_puzzle();
+    assert_eq!(board.count_solutions(2), 1);
+    assert_eq!(board.count_solutions(0), 0);
+    let empty_board: SudokuBoard = ".".repeat(81).parse().unwrap();
+    assert_eq!(empty_board.count_solutions(2), 2);
+    assert_eq!(empty_board.count_solutions(5), 5);
+}
+
+#[test]
+fn test_has_unique_solution() {
+    let mut board: SudokuBoard =
+        "9..4...8......8.2.7.1......1.5..3..2...9..4..2.6....9..5.6...3...28....6....72..."
+            .parse()
+            .unwrap();
+    assert!(board.has_unique_solution());
+    let empty_board: SudokuBoard = ".".repeat(81).parse().unwrap();
+    assert!(!empty_board.has_unique_solution());
+    // every clue of this puzzle is needed
+    board[(0, 0)] = None;
+    assert!(!board.has_unique_solution());
+}
+
+#[test]
+fn test_solutions() {
+    let solved_board: SudokuBoard = "238597416
+694231857
+517864239
+173429685
+862315974
+945678321
+459182763
+786953142
+321746598"
+        .parse()
+        .unwrap();
+    let solutions = solved_board.solutions().collect::<Vec<_>>();
+    assert_eq!(solutions.len(), 1);
+    assert_eq!(solutions[0], solved_board);
+    // nothing is left for the top right corner
+    let contradictory_board: SudokuBoard =
+        "12345678.........9..............................................................."
+            .parse()
+            .unwrap();
+    assert_eq!(contradictory_board.solutions().count(), 0);
+    let ambiguous_board: SudokuBoard =
+        "........2..5..8....7...1..6...9..2...35.2.....9.....8..6..4..9.....1.5.......8..."
+            .parse()
+            .unwrap();
+    for solution in ambiguous_board.solutions().take(10) {
+        assert_eq!(solution.validate(), Ok(()));
+    }
+}
+
+#[test]
+fn test_generate_full() {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let board = SudokuBoard::generate_full(&mut StdRng::seed_from_u64(7));
+    assert!(board.is_solution());
+    assert_eq!(board.validate(), Ok(()));
+    let same_seed_board = SudokuBoard::generate_full(&mut StdRng::seed_from_u64(7));
+    assert_eq!(board, same_seed_board);
+    let other_seed_board = SudokuBoard::generate_full(&mut StdRng::seed_from_u64(8));
+    assert_ne!(board, other_seed_board);
+}
+
+#[test]
+fn test_generate_puzzle() {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(7);
+    let puzzle = SudokuBoard::generate_puzzle(&mut rng, 30);
+    assert!(puzzle.has_unique_solution());
+    assert_eq!(puzzle.validate(), Ok(()));
+    assert!(puzzle.iter().filter(|space| space.is_some()).count() >= 30);
+    let minimal_puzzle = SudokuBoard::generate_puzzle(&mut rng, 0);
+    assert!(minimal_puzzle.has_unique_solution());
+    assert!(
+        minimal_puzzle
+            .iter()
+            .filter(|space| space.is_some())
+            .count()
+            < 30
+    );
+}
+
+#[test]
+fn test_minimize() {
+    let board = easy_puzzle();
+    let solution = board.solve().expect("Sudoku board has a solution");
+    // the first row filled in from the solution
+    let over_clued = Board::from_fn(|(x, y)| {
+        if y == 0 {
+            solution[(x, y)]
+        } else {
+            board[(x, y)]
+        }
+    });
+
+    let minimal = over_clued.minimize();
+    assert!(minimal.clue_count() < board.clue_count());
+    assert_eq!(minimal.solve(), Some(solution.clone()));
+    assert!(minimal.has_unique_solution());
+    assert!(minimal
+        .diff(&over_clued)
+        .iter()
+        .all(|&(_, kept, _)| kept.is_none()));
+    for pos in SudokuBoard::iter_positions().filter(|&pos| minimal[pos].is_some()) {
+        let mut fewer = minimal.clone();
+        fewer[pos] = None;
+        assert!(!fewer.has_unique_solution());
+    }
+
+    assert_eq!(SudokuBoard::empty().minimize(), SudokuBoard::empty());
+}
+
+#[test]
+fn test_generate_symmetric() {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(7);
+    let puzzle = SudokuBoard::generate_symmetric(&mut rng, Symmetry::Rotational180);
+    assert!(puzzle.has_unique_solution());
+    assert!(puzzle.clue_count() < 40);
+    for (x, y) in SudokuBoard::iter_positions() {
+        assert_eq!(
+            puzzle[(x, y)].is_some(),
+            puzzle[(SIDE - 1 - x, SIDE - 1 - y)].is_some()
+        );
+    }
+    let clues = |board: &SudokuBoard| board.to_2d().map(|row| row.map(|space| space.is_some()));
+    assert_eq!(clues(&puzzle.rotate180()), clues(&puzzle));
+
+    let puzzle = SudokuBoard::generate_symmetric(&mut rng, Symmetry::Rotational90);
+    assert!(puzzle.has_unique_solution());
+    assert_eq!(clues(&puzzle.rotate90()), clues(&puzzle));
+    let puzzle = SudokuBoard::generate_symmetric(&mut rng, Symmetry::Mirror);
+    assert!(puzzle.has_unique_solution());
+    assert_eq!(clues(&puzzle.reflect_horizontal()), clues(&puzzle));
+    let puzzle = SudokuBoard::generate_symmetric(&mut rng, Symmetry::Diagonal);
+    assert!(puzzle.has_unique_solution());
+    let transposed = Board::from_fn(|(x, y)| puzzle[(y, x)]);
+    assert_eq!(clues(&transposed), clues(&puzzle));
+
+    assert_eq!(Symmetry::Rotational90.orbit((0, 0)).len(), 4);
+    assert_eq!(Symmetry::Rotational90.orbit((4, 4)), vec![(4, 4)]);
+    assert_eq!(Symmetry::Mirror.orbit((1, 2)), vec![(1, 2), (7, 2)]);
+}
+
+#[test]
+fn test_generate_rated() {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(7);
+    for target in [Difficulty::Easy, Difficulty::Hard, Difficulty::Extreme] {
+        let puzzle = SudokuBoard::generate_rated(&mut rng, target);
+        assert_eq!(puzzle.difficulty(), target);
+        assert!(puzzle.has_unique_solution());
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_daily() {
+    let date = NaiveDate::from_ymd_opt(2024, 3, 14).unwrap();
+    let puzzle = SudokuBoard::daily(date, Difficulty::Medium);
+    assert_eq!(puzzle.difficulty(), Difficulty::Medium);
+    assert!(puzzle.has_unique_solution());
+    assert_eq!(
+        SudokuBoard::daily(date, Difficulty::Medium).to_string(),
+        puzzle.to_string()
+    );
+    assert_ne!(SudokuBoard::daily(date, Difficulty::Easy), puzzle);
+    assert_ne!(
+        SudokuBoard::daily(date.succ_opt().unwrap(), Difficulty::Medium),
+        puzzle
+    );
+}
+
+#[test]
+fn test_to_2d() {
+    let board = easy_puzzle();
+    let rows = board.to_2d();
+    assert_eq!(rows[0][1], Some(Space::Three));
+    assert_eq!(rows[1][0], Some(Space::Six));
+    assert_eq!(rows[8][8], Some(Space::Nine));
+    assert_eq!(rows[0][2], None);
+    assert_eq!(SudokuBoard::from(rows), board);
+
+    let mut rows = [[None; SIDE]; SIDE];
+    rows[3][5] = Some(Space::Seven);
+    let board = SudokuBoard::from(rows);
+    assert_eq!(board[(5, 3)], Some(Space::Seven));
+    assert_eq!(board.clue_count(), 1);
+    assert_eq!(board.to_2d(), rows);
+}
+
+#[test]
+fn test_to_grid_string() {
+    let board = easy_puzzle();
+    let expected = "\
+┌───┬───┬───┐
+│53.│.7.│...│
+│6..│195│...│
+│.98│...│.6.│
+├───┼───┼───┤
+│8..│.6.│..3│
+│4..│8.3│..1│
+│7..│.2.│..6│
+├───┼───┼───┤
+│.6.│...│28.│
+│...│419│..5│
+│...│.8.│.79│
+└───┴───┴───┘";
+    assert_eq!(board.to_grid_string(), expected);
+}
+
+#[test]
+fn test_display_colored() {
+    let board = easy_puzzle();
+    let solution = board.solve().expect("Sudoku board has a solution");
+    assert_eq!(
+        solution.display_colored_with(&board, false),
+        solution.to_string()
+    );
+    assert_eq!(board.display_colored_with(&board, false), board.to_string());
+
+    let colored = solution.display_colored_with(&board, true);
+    let first_line = colored.lines().next().unwrap();
+    assert!(first_line.starts_with("\x1b[1;34m5\x1b[0m\x1b[1;34m3\x1b[0m\x1b[32m4\x1b[0m"));
+    assert_eq!(colored.matches("\x1b[1;34m").count(), board.clue_count());
+    assert_eq!(colored.matches("\x1b[32m").count(), board.empty_count());
+}
+
+#[test]
+fn test_clue_count() {
+    let board = easy_puzzle();
+    assert_eq!(board.clue_count(), 30);
+    assert_eq!(board.empty_count(), 51);
+}
+
+#[test]
+fn test_iter_filled() {
+    let board = easy_puzzle();
+    let filled = board.iter_filled().collect::<Vec<_>>();
+    let empty = board.iter_empty().collect::<Vec<_>>();
+    assert_eq!(filled.len() + empty.len(), CELLS);
+    assert_eq!(filled.len(), board.clue_count());
+    assert_eq!(filled[..2], [((0, 0), Space::Five), ((1, 0), Space::Three)]);
+    assert_eq!(empty[0], (2, 0));
+    for (pos, space) in filled {
+        assert_eq!(board[pos], Some(space));
+    }
+    for pos in empty {
+        assert_eq!(board[pos], None);
+    }
+}
+
+#[test]
+fn test_conflicts() {
+    let mut board = easy_puzzle();
+    assert_eq!(board.conflicts(), vec![]);
+    assert_eq!(board.validate(), Ok(()));
+
+    // clashes with the 5 in the same row and the same square
+    board[(2, 0)] = Some(Space::Five);
+    let pos = |x, y| Pos::new(x, y).unwrap();
+    assert_eq!(board.conflicts(), vec![(Space::Five, pos(0, 0), pos(2, 0))]);
+    assert_eq!(
+        board.validate(),
+        Err(String::from("Row 0 is invalid: duplicate Five"))
+    );
+
+    // clashes with the 4 further up the column
+    board[(0, 8)] = Some(Space::Four);
+    assert_eq!(
+        board.conflicts(),
+        vec![
+            (Space::Five, pos(0, 0), pos(2, 0)),
+            (Space::Four, pos(0, 4), pos(0, 8))
+        ]
+    );
+}
+
+#[test]
+fn test_diff() {
+    let board = easy_puzzle();
+    assert_eq!(board.diff(&board), vec![]);
+
+    let pos = |x, y| Pos::new(x, y).unwrap();
+    let solution = board.solve().expect("Sudoku board has a solution");
+    let diff = board.diff(&solution);
+    assert_eq!(diff.len(), board.empty_count());
+    assert_eq!(diff[0], (pos(2, 0), None, Some(Space::Four)));
+    assert!(diff
+        .iter()
+        .all(|&(pos, old, new)| old.is_none() && new == solution[pos]));
+
+    let mut attempt = board.clone();
+    attempt[(2, 0)] = Some(Space::One);
+    attempt[(0, 0)] = None;
+    assert_eq!(
+        board.diff(&attempt),
+        vec![
+            (pos(0, 0), Some(Space::Five), None),
+            (pos(2, 0), None, Some(Space::One))
+        ]
+    );
+}
+
+#[test]
+fn test_is_solvable() {
+    let board = easy_puzzle();
+    assert!(board.is_solvable());
+    assert!(board.solve().unwrap().is_solvable());
+    assert!(SudokuBoard::empty().is_solvable());
+
+    // nothing is left for the top left corner, with a 9 below it
+    let doomed: SudokuBoard =
+        ".123456789......................................................................."
+            .parse()
+            .unwrap();
+    assert_eq!(doomed.validate(), Ok(()));
+    assert!(!doomed.is_solvable());
+
+    // a 1 that fits its row, column and square but isn't in the solution
+    let mut doomed = board.clone();
+    doomed[(2, 0)] = Some(Space::One);
+    assert_eq!(doomed.validate(), Ok(()));
+    assert!(!doomed.is_solvable());
+
+    let mut duplicated = board.clone();
+    duplicated[(2, 0)] = Some(Space::Five);
+    assert!(duplicated.validate().is_err());
+    assert!(!duplicated.is_solvable());
+}
+
+#[test]
+fn test_unsolvable_reason() {
+    let board = easy_puzzle();
+    assert_eq!(board.unsolvable_reason(), None);
+
+    // nothing is left for the top left corner, with a 9 below it
+    let forced_empty: SudokuBoard =
+        ".123456789......................................................................."
+            .parse()
+            .unwrap();
+    assert_eq!(
+        forced_empty.unsolvable_reason(),
+        Some(String::from("Cell (0, 0) has no remaining candidates"))
+    );
+
+    let mut duplicated = board.clone();
+    duplicated[(2, 0)] = Some(Space::Five);
+    assert_eq!(
+        duplicated.unsolvable_reason(),
+        Some(String::from("Row 0 is invalid: duplicate Five"))
+    );
+
+    // the 9 below leaves the end of the top row nothing
+    let no_candidates: SudokuBoard =
+        "12345678.........9..............................................................."
+            .parse()
+            .unwrap();
+    assert_eq!(
+        no_candidates.unsolvable_reason(),
+        Some(String::from("Cell (8, 0) has no remaining candidates"))
+    );
+
+    // the 9s in the next two rows shut it out of the top row's free cells
+    let no_place: SudokuBoard =
+        "1234..........9...........9......................................................"
+            .parse()
+            .unwrap();
+    assert_eq!(
+        no_place.unsolvable_reason(),
+        Some(String::from("Value 9 can't be placed anywhere in row 0"))
+    );
+
+    // a 1 that fits its row, column and square but isn't in the solution
+    let mut doomed = board.clone();
+    doomed[(2, 0)] = Some(Space::One);
+    assert!(doomed.solve().is_none());
+    assert!(doomed.unsolvable_reason().is_some());
+}
+
+#[test]
+fn test_set_cell() {
+    let mut board = easy_puzzle();
+    let pos = Pos::new(2, 0).unwrap();
+    assert_eq!(board.set_cell(pos, Space::Four), Ok(()));
+    assert_eq!(board[(2, 0)], Some(Space::Four));
+
+    // the 5 at the start of the row, and the 9 in the same square
+    assert_eq!(
+        board.set_cell(pos, Space::Five),
+        Err(Conflict {
+            space: Space::Five,
+            pos,
+            peer: Pos::new(0, 0).unwrap(),
+        })
+    );
+    assert_eq!(
+        board.set_cell(pos, Space::Nine).unwrap_err().peer,
+        Pos::new(1, 2).unwrap()
+    );
+    assert_eq!(board[(2, 0)], Some(Space::Four));
+
+    // a cell's own value never conflicts with itself
+    assert_eq!(board.set_cell(pos, Space::Four), Ok(()));
+    assert_eq!(board.clear_cell(pos), Some(Space::Four));
+    assert_eq!(board.clear_cell(pos), None);
+    assert_eq!(board.validate(), Ok(()));
+}
+
+#[test]
+fn test_is_solution_checks_validity() {
+    let mut board = easy_solution();
+    assert!(board.is_solution());
+    board[(0, 0)] = Some(Space::Three);
+    assert!(board.is_complete());
+    assert!(!board.is_solution());
+    assert_eq!(board.solve(), None);
+}
+
+#[test]
+fn test_candidates_at() {
+    let board = easy_puzzle();
+    // row 0 has 3, 5, 7; column 2 has 8; the square adds 6 and 9
+    let candidates = board.candidates_at(Pos::new(2, 0).unwrap());
+    assert_eq!(
+        candidates.iter().collect::<Vec<_>>(),
+        vec![Space::One, Space::Two, Space::Four]
+    );
+    assert_eq!(
+        board.candidates_at(Pos::new(0, 0).unwrap()),
+        SudokuChoices::one(Space::Five)
+    );
+}
+
+#[test]
+fn test_peers() {
+    let center = Pos::new(4, 4).unwrap();
+    let peers = SudokuBoard::peers(center).collect::<Vec<_>>();
+    assert_eq!(peers.len(), 20);
+    for (i, &pos) in peers.iter().enumerate() {
+        assert!(!peers[i + 1..].contains(&pos));
+        assert_ne!(pos, center);
+        assert!(pos.x() == 4 || pos.y() == 4 || SudokuRegion::square_of(pos.into()) == Square(4));
+    }
+}
+
+#[test]
+fn test_pos() {
+    let pos = Pos::new(2, 7).unwrap();
+    assert_eq!((pos.x(), pos.y()), (2, 7));
+    assert_eq!(pos.index(), 7 * SIDE + 2);
+    assert_eq!(Pos::from_index(pos.index()), Some(pos));
+    assert_eq!(BoardPosition::from(pos), (2, 7));
+    assert_eq!(pos.to_string(), "(2, 7)");
+    assert_eq!(Pos::new(SIDE, 0), None);
+    assert_eq!(Pos::new(0, SIDE), None);
+    assert_eq!(Pos::from_index(CELLS), None);
+
+    let positions = Pos::all().collect::<Vec<_>>();
+    assert_eq!(positions.len(), CELLS);
+    assert!(positions
+        .iter()
+        .enumerate()
+        .all(|(i, pos)| pos.index() == i));
+
+    let board = easy_puzzle();
+    // indexing by either form reads the same cell
+    assert_eq!(board[pos], board[(2, 7)]);
+    assert_eq!(board[Pos::new(1, 0).unwrap()], Some(Space::Three));
+}
+
+#[test]
+fn test_region_accessors() {
+    let board = easy_puzzle();
+    let (bx, by) = (4 / BOX_SIZE, 4 / BOX_SIZE);
+    let positions = board.box_at(bx, by).map(|(pos, _)| pos).collect::<Vec<_>>();
+    assert_eq!(
+        positions,
+        vec![
+            (3, 3),
+            (4, 3),
+            (5, 3),
+            (3, 4),
+            (4, 4),
+            (5, 4),
+            (3, 5),
+            (4, 5),
+            (5, 5)
+        ]
+    );
+    let values = board
+        .box_at(bx, by)
+        .filter_map(|(_, space)| *space)
+        .map(usize::from)
+        .collect::<Vec<_>>();
+    assert_eq!(values, vec![6, 8, 3, 2]);
+
+    assert_eq!(board.row(0).nth(1), Some(((1, 0), &Some(Space::Three))));
+    assert_eq!(board.column(0).nth(1), Some(((0, 1), &Some(Space::Six))));
+}
+
+#[test]
+fn test_space_all() {
+    let all = Space::all();
+    assert_eq!(all.len(), 9);
+    for (i, space) in all.into_iter().enumerate() {
+        assert_eq!(usize::from(space), i + 1);
+    }
+    assert_eq!(SudokuChoices::all().iter().collect::<Vec<_>>(), all);
+}
+
+#[test]
+fn test_solve_checked() {
+    let board = easy_puzzle();
+    assert_eq!(
+        board.solve_checked(),
+        SolveResult::Unique(board.solve().unwrap())
+    );
+
+    let empty_board: SudokuBoard = ".".repeat(81).parse().unwrap();
+    match empty_board.solve_checked() {
+        SolveResult::Multiple(solution) => assert!(solution.is_solution()),
+        result => panic!("expected multiple solutions, got {result:?}"),
+    }
+
+    let mut conflicting = board.clone();
+    conflicting[(2, 0)] = Some(Space::Five);
+    assert_eq!(conflicting.solve_checked(), SolveResult::NoSolution);
+}
+
+#[test]
+fn test_is_valid_solution() {
+    let board = easy_puzzle();
+    let solution = board.solve().unwrap();
+    let agrees = |board: &SudokuBoard| {
+        board.is_valid_solution() == (board.is_complete() && board.validate().is_ok())
+    };
+
+    assert!(solution.is_valid_solution());
+    assert!(agrees(&solution));
+    assert!(!board.is_valid_solution());
+    assert!(agrees(&board));
+
+    let mut swapped = solution.clone();
+    swapped[(0, 0)] = solution[(1, 0)];
+    swapped[(1, 0)] = solution[(0, 0)];
+    assert!(!swapped.is_valid_solution());
+    assert!(agrees(&swapped));
+
+    let mut repeated = solution.clone();
+    repeated[(4, 4)] = repeated[(4, 5)];
+    assert!(!repeated.is_valid_solution());
+    assert!(agrees(&repeated));
+}
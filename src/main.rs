@@ -1,736 +1,330 @@
-#![feature(int_roundings)]
-#![feature(generic_arg_infer)]
-#![feature(array_chunks)]
 use std::{
-    fmt::Display,
-    iter::empty,
-    ops::{Index, IndexMut},
-    str::FromStr,
+    env, fs,
+    io::{self, BufRead, Read, Write},
+    ops::ControlFlow,
+    process::ExitCode,
 };
 
-use space_search::{Scoreable, Searchable, SolutionIdentifiable};
-
-#[derive(Clone, Debug, Hash, PartialEq, Eq)]
-struct Board<Cell>([Cell; 81]);
-
-impl<Cell> Board<Cell> {
-    fn iter(&self) -> impl Iterator<Item = &Cell> {
-        self.0.iter()
-    }
-
-    fn iter_positions() -> impl Iterator<Item = BoardPosition> {
-        (0..81).map(|i| (i % 9, i / 9))
-    }
-}
-
-type BoardPosition = (usize, usize);
-
-impl<Cell> Index<BoardPosition> for Board<Cell> {
-    type Output = Cell;
-
-    fn index(&self, (x, y): BoardPosition) -> &Self::Output {
-        &self.0[y * 9 + x]
-    }
-}
-
-impl<Cell> IndexMut<BoardPosition> for Board<Cell> {
-    fn index_mut(&mut self, (x, y): BoardPosition) -> &mut Self::Output {
-        &mut self.0[y * 9 + x]
-    }
-}
-
-#[derive(Clone, Debug, Copy, Hash, PartialEq, Eq)]
-enum Space {
-    One,
-    Two,
-    Three,
-    Four,
-    Five,
-    Six,
-    Seven,
-    Eight,
-    Nine,
-}
-
-impl Space {
-    fn idx(&self) -> usize {
-        let space_number: usize = (*self).into();
-        space_number - 1
-    }
-}
-
-impl From<Space> for usize {
-    fn from(value: Space) -> Self {
-        use Space::*;
-        match value {
-            One => 1,
-            Two => 2,
-            Three => 3,
-            Four => 4,
-            Five => 5,
-            Six => 6,
-            Seven => 7,
-            Eight => 8,
-            Nine => 9,
-        }
-    }
-}
-
-impl TryFrom<usize> for Space {
-    type Error = String;
-
-    fn try_from(value: usize) -> Result<Self, Self::Error> {
-        use Space::*;
-        let result = match value {
-            1 => One,
-            2 => Two,
-            3 => Three,
-            4 => Four,
-            5 => Five,
-            6 => Six,
-            7 => Seven,
-            8 => Eight,
-            9 => Nine,
-            _ => return Err(format!("Cant convert '{}' to a space value", value)),
-        };
-        Ok(result)
-    }
-}
-
-type SudokuBoard = Board<Option<Space>>;
-
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
-struct SudokuChoices(u16);
-
-impl SudokuChoices {
-    fn all() -> Self {
-        SudokuChoices(0b111111111)
-    }
-
-    fn none() -> Self {
-        SudokuChoices(0b000000000)
-    }
-
-    fn one(space: Space) -> Self {
-        let mut choices = SudokuChoices::none();
-        choices.set(space, true);
-        choices
-    }
-
-    fn new(initial_choice: Option<Space>) -> Self {
-        match initial_choice {
-            Some(space) => SudokuChoices::one(space),
-            None => SudokuChoices::all(),
+use sudoku::{HintReason, Pos, Space, SudokuBoard, SudokuRegion, CELLS};
+
+const USAGE: &str = "\
+usage: sudoku [--format=line|grid] <file>   solve the puzzles in a file
+       sudoku [--format=line|grid] -        solve the puzzles on stdin
+       sudoku repl                          solve interactively
+Input is either one puzzle per line or a single grid over nine lines.";
+
+/// How solutions are printed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    /// All 81 digits on one line.
+    Line,
+    /// Boxed in with borders, as by `to_grid_string`.
+    Grid,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Solve { path: String, format: Format },
+    Repl,
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut format = Format::Line;
+    let mut path = None;
+    for arg in args {
+        match arg.as_str() {
+            "--format=line" => format = Format::Line,
+            "--format=grid" => format = Format::Grid,
+            "repl" if path.is_none() => return Ok(Command::Repl),
+            "-" => path = Some(arg),
+            _ if arg.starts_with('-') => return Err(format!("Unknown option {arg}")),
+            _ if path.is_some() => return Err(String::from("Only one input can be given")),
+            _ => path = Some(arg),
         }
     }
-
-    fn iter(&self) -> impl Iterator<Item = Space> + '_ {
-        (0..9).filter_map(|i| {
-            ((1 << i) & self.0 != 0).then(|| Space::try_from(i + 1).unwrap())
-        })
-    }
-
-    fn set(&mut self, space: Space, value: bool) {
-        if value {
-            self.0 |= 1 << space.idx();
-        } else {
-            self.0 &= !(1 << space.idx());
+    let path = path.ok_or_else(|| String::from("No input given"))?;
+    Ok(Command::Solve { path, format })
+}
+
+const HELP: &str = "\
+commands:
+  <puzzle>          load an 81-character puzzle
+  show              print the board
+  hint              describe the next forced placement
+  step              make the next forced placement
+  solve             print the solution
+  candidates x y    list the values cell (x, y) could take
+  set x y v         place v at (x, y)
+  clear x y         empty cell (x, y)
+  help              print this message
+  quit              leave
+x and y count from 0 at the top left.";
+
+fn main() -> ExitCode {
+    match parse_args(env::args().skip(1)) {
+        Ok(Command::Solve { path, format }) => solve_input(&path, format),
+        Ok(Command::Repl) => repl(),
+        Err(err) => {
+            eprintln!("{err}\n{USAGE}");
+            ExitCode::FAILURE
         }
     }
 }
 
-impl Index<Space> for SudokuChoices {
-    type Output = bool;
-
-    fn index(&self, index: Space) -> &Self::Output {
-        // this is stupid, this shouldnt work :/
-        if self.0 & (1 << index.idx()) != 0 {
-            &true
-        } else {
-            &false
+/// Solves every puzzle in the file at `path`, or on stdin for `-`. Fails if
+/// any puzzle couldn't be read.
+fn solve_input(path: &str, format: Format) -> ExitCode {
+    let input = match path {
+        "-" => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input).map(|_| input)
         }
-    }
-}
-
-impl Display for SudokuChoices {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "[{}]",
-            (0..9)
-                .map(|i| if self.0 & (1 << i) != 0 {
-                    (i + 1).to_string()
-                } else {
-                    " ".to_string()
-                })
-                .collect::<Vec<_>>()
-                .join("")
-        )
-    }
-}
-
-type PossibilitySpaceBoard = Board<SudokuChoices>;
-impl PossibilitySpaceBoard {
-    fn new(board: &SudokuBoard) -> Self {
-        Board(board.0.map(SudokuChoices::new))
-    }
-}
-
-impl Display for PossibilitySpaceBoard {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            (0..9)
-                .map(|y| {
-                    (0..9)
-                        .map(|x| format!("{}", &self[(x, y)]))
-                        .collect::<Vec<_>>()
-                        .join(" ")
-                })
-                .collect::<Vec<_>>()
-                .join("\n")
-        )
-    }
-}
-
-#[derive(Debug)]
-enum SudokuRegion {
-    Column(usize),
-    Row(usize),
-    Square(usize),
-}
-use SudokuRegion::*;
-
-impl SudokuRegion {
-    fn row_of((_, y): BoardPosition) -> SudokuRegion {
-        Row(y)
-    }
-
-    fn column_of((x, _): BoardPosition) -> SudokuRegion {
-        Column(x)
-    }
-
-    fn square_of((x, y): BoardPosition) -> SudokuRegion {
-        Square((y / 3) * 3 + (x / 3))
-    }
-}
-
-impl IntoIterator for SudokuRegion {
-    type Item = BoardPosition;
-
-    type IntoIter = SudokuRegionIter;
-
-    fn into_iter(self) -> Self::IntoIter {
-        SudokuRegionIter {
-            region: self,
-            index: Some(0),
+        _ => fs::read_to_string(path),
+    };
+    let input = match input {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Couldn't read {path}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // a first line too short for a whole board starts a multi-line grid
+    let one_per_line = input
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .is_some_and(|line| line.trim().chars().count() >= CELLS);
+    let results = match one_per_line {
+        true => SudokuBoard::solve_many(input.as_bytes()).collect::<Vec<_>>(),
+        false => vec![input
+            .trim_end()
+            .parse::<SudokuBoard>()
+            .map(|board| board.solve())],
+    };
+
+    let mut status = ExitCode::SUCCESS;
+    for (i, result) in results.into_iter().enumerate() {
+        match result {
+            Ok(Some(solution)) => match format {
+                Format::Line => println!("{}", solution.to_string().replace('\n', "")),
+                Format::Grid => println!("{}", solution.to_grid_string()),
+            },
+            Ok(None) => println!("no solution"),
+            Err(err) => {
+                eprintln!("puzzle {}: {err}", i + 1);
+                status = ExitCode::FAILURE;
+            }
         }
     }
-}
-
-struct SudokuRegionIter {
-    region: SudokuRegion,
-    index: Option<usize>,
-}
-
-impl Iterator for SudokuRegionIter {
-    type Item = BoardPosition;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let index = self.index?;
-        let next_pos = match &self.region {
-            &Column(col) => (col, index),
-            &Row(row) => (index, row),
-            &Square(square) => (
-                (square % 3) * 3 + (index % 3),
-                (square / 3) * 3 + (index / 3),
-            ),
+    status
+}
+
+fn repl() -> ExitCode {
+    println!("Type a puzzle to start, or help for commands.");
+    let mut board = None;
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        print!("> ");
+        io::stdout().flush().ok();
+        let Some(Ok(line)) = lines.next() else {
+            break;
         };
-        self.index = (index < 8).then_some(index + 1);
-        Some(next_pos)
+        match execute(&mut board, &line) {
+            ControlFlow::Continue(reply) if reply.is_empty() => {}
+            ControlFlow::Continue(reply) => println!("{reply}"),
+            ControlFlow::Break(()) => break,
+        }
     }
-}
-
-impl SudokuBoard {
-    fn reduce(&mut self) -> (PossibilitySpaceBoard, bool) {
-        fn set(
-            board: &mut SudokuBoard,
-            possibilities_board: &mut PossibilitySpaceBoard,
-            pos: BoardPosition,
-            space: Space,
-        ) -> bool {
-            let mut is_invalid = false;
-            if board[pos].is_none() {
-                board[pos] = Some(space);
-                possibilities_board[pos] = SudokuChoices::one(space);
-
-                for pos in empty()
-                    .chain(SudokuRegion::row_of(pos))
-                    .chain(SudokuRegion::column_of(pos))
-                    .chain(SudokuRegion::square_of(pos))
-                    .filter(|p| p != &pos)
-                {
-                    possibilities_board[pos].set(space, false);
-                    let remaining_possibilities =
-                        possibilities_board[pos].iter().take(2).collect::<Vec<_>>();
-                    is_invalid = match &remaining_possibilities[..] {
-                        &[] => true,
-                        &[only] if board[pos].is_none() => {
-                            set(board, possibilities_board, pos, only)
-                        }
-                        _ => false,
-                    };
-                    if is_invalid {
-                        break;
-                    }
-                }
-            } else {
-                unimplemented!(
-                    "Not allowed to change the value of an already set space: {:?} to {:?} at {:?}",
-                    board[pos],
-                    space,
-                    pos
-                );
+    ExitCode::SUCCESS
+}
+
+/// Runs one REPL command against `board`, returning what to print, or
+/// `Break` to leave.
+fn execute(board: &mut Option<SudokuBoard>, line: &str) -> ControlFlow<(), String> {
+    let words = line.split_whitespace().collect::<Vec<_>>();
+    let reply = match words[..] {
+        [] => String::new(),
+        ["quit" | "exit"] => return ControlFlow::Break(()),
+        ["help"] => String::from(HELP),
+        [command, ref args @ ..] => match (command, board.as_mut()) {
+            ("show" | "hint" | "step" | "solve" | "candidates" | "set" | "clear", None) => {
+                String::from("No puzzle loaded yet")
             }
-            is_invalid
-        }
-
-        let mut possibilities_board = PossibilitySpaceBoard::new(self);
-
-        let mut is_invalid = false;
-
-        'outer: loop {
-            let mut adjusted = false;
-
-            for pos in SudokuBoard::iter_positions() {
-                let mut new_possibilities = possibilities_board[pos].clone();
-
-                if self[pos].is_none() {
-                    for region in [
-                        SudokuRegion::row_of(pos),
-                        SudokuRegion::column_of(pos),
-                        SudokuRegion::square_of(pos),
-                    ] {
-                        let mut solo_candidates = new_possibilities.clone();
-                        for pos in region
-                            .into_iter()
-                            .filter(|p| p != &pos) 
-                        {
-                            if let Some(space) = self[pos] {
-                                new_possibilities.set(space, false);
-                            }
-                            for space in possibilities_board[pos].iter() {
-                                solo_candidates.set(space, false);
-                            }
-                        }
-                        if let &[value] = &solo_candidates.iter().take(2).collect::<Vec<_>>()[..] {
-                            new_possibilities = SudokuChoices::one(value);
-                            break;
-                        }
-                    }
-
-                    // update possibility space
-                    adjusted |= new_possibilities != possibilities_board[pos];
-                    possibilities_board[pos] = new_possibilities;
+            ("show", Some(board)) => board.to_grid_string(),
+            ("hint", Some(board)) => match board.hint() {
+                Some(hint) => format!(
+                    "{} goes at {}: {}",
+                    usize::from(hint.space),
+                    format_pos(hint.pos),
+                    describe(hint.reason)
+                ),
+                None => String::from("No placement is forced by singles"),
+            },
+            ("step", Some(board)) => match board.hint() {
+                Some(hint) => {
+                    board[hint.pos] = Some(hint.space);
+                    format!(
+                        "Placed {} at {}\n{}",
+                        usize::from(hint.space),
+                        format_pos(hint.pos),
+                        board.to_grid_string()
+                    )
                 }
-
-                // confirm square if all alternative possibilities are exhausted
-                let remaining_possibilities = new_possibilities.iter().take(2).collect::<Vec<_>>();
-                match &remaining_possibilities[..] {
-                    &[] => {
-                        is_invalid = true;
-                    }
-                    &[value] if self[pos].is_none() => {
-                        is_invalid |= set(self, &mut possibilities_board, pos, value);
-                    }
-                    _ => {}
-                }
-                if is_invalid {
-                    break 'outer;
+                None => String::from("No placement is forced by singles"),
+            },
+            ("solve", Some(board)) => match board.solve() {
+                Some(solution) => solution.to_grid_string(),
+                None => board
+                    .unsolvable_reason()
+                    .unwrap_or_else(|| String::from("No solution")),
+            },
+            ("candidates", Some(board)) => match parse_pos(args) {
+                Ok(pos) => board
+                    .candidates_at(pos)
+                    .iter()
+                    .map(|space| usize::from(space).to_string())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                Err(err) => err,
+            },
+            ("set", Some(board)) => {
+                let placement = match args {
+                    [x, y, value] => parse_pos(&[x, y]).and_then(|pos| {
+                        let space = value
+                            .parse::<usize>()
+                            .ok()
+                            .and_then(|value| Space::try_from(value).ok())
+                            .ok_or(format!("{value} isn't a value from 1 to 9"))?;
+                        Ok((pos, space))
+                    }),
+                    _ => Err(String::from("usage: set x y v")),
+                };
+                match placement {
+                    Ok((pos, space)) => match board.set_cell(pos, space) {
+                        Ok(()) => board.to_grid_string(),
+                        Err(conflict) => conflict.to_string(),
+                    },
+                    Err(err) => err,
                 }
             }
-
-            if !adjusted || self.is_solution() {
-                break;
-            }
-        }
-
-        (possibilities_board, is_invalid)
-    }
-
-    #[allow(unused)]
-    fn validate(&self) -> Result<(), String> {
-        fn verify_set(it: impl Iterator<Item = Space>) -> Result<(), Space> {
-            let mut choices = SudokuChoices::none();
-            for space in it {
-                if choices[space] {
-                    return Err(space);
+            ("clear", Some(board)) => match parse_pos(args) {
+                Ok(pos) => {
+                    board.clear_cell(pos);
+                    board.to_grid_string()
                 }
-                choices.set(space, true);
-            }
-            Ok(())
-        }
-        for i in 0..9 {
-            let space_at = |pos| self[pos];
-            if let Err(invalid_space) = verify_set(Row(i).into_iter().filter_map(space_at)) {
-                Err(format!("Row {i} is invalid: duplicate {invalid_space:?}"))?;
-            }
-            if let Err(invalid_space) = verify_set(Column(i).into_iter().filter_map(space_at)) {
-                Err(format!(
-                    "Column {i} is invalid: duplicate {invalid_space:?}"
-                ))?;
-            }
-            if let Err(invalid_space) = verify_set(Square(i).into_iter().filter_map(space_at)) {
-                Err(format!(
-                    "Square {i} is invalid: duplicate {invalid_space:?}"
-                ))?;
-            }
-        }
-        Ok(())
-    }
-}
-
-impl FromStr for SudokuBoard {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let collect = s
-            .split('\n')
-            .flat_map(|row| {
-                row.chars()
-                    .map(|chr| match chr {
-                        ' ' => Ok(None),
-                        '1'..='9' => Ok(Some(
-                            Space::try_from(
-                                chr.to_digit(10)
-                                    .expect("char will always be convertible to a digit")
-                                    as usize,
-                            )
-                            .expect("char will always be convertible to a digit"),
-                        )),
-                        _ => Err(format!("Character '{chr}' is not valid for a sudoku board")),
-                    })
-                    .collect::<Result<Vec<_>, _>>()
-            })
-            .flatten()
-            .collect::<Vec<_>>();
-        let space_count = collect.len();
-        Ok(Board(collect.try_into().map_err(|_| {
-            format!("Incorrect number of spaces on sudoku board: expected 81, found {space_count}")
-        })?))
-    }
-}
-
-impl Display for SudokuBoard {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            self.0
-                .array_chunks()
-                .map(|row: &[_; 9]| {
-                    row.iter()
-                        .map(|space| match space {
-                            None => String::from(" "),
-                            Some(space) => format!("{}", usize::from(*space)),
-                        })
-                        .collect::<Vec<_>>()
-                        .join("")
-                })
-                .collect::<Vec<_>>()
-                .join("\n")
-        )
-    }
-}
-
-enum NextBoardStates<I> {
-    Single(Option<SudokuBoard>),
-    States(I),
-}
-
-impl<I> Iterator for NextBoardStates<I>
-where
-    I: Iterator<Item = SudokuBoard>,
-{
-    type Item = SudokuBoard;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        match self {
-            NextBoardStates::Single(board) => board.take(),
-            NextBoardStates::States(iter) => iter.next(),
-        }
-    }
-}
-
-impl Searchable for SudokuBoard {
-    fn next_states(&self) -> impl Iterator<Item = Self> {
-        let mut reduced_board = self.clone();
-        let (possibilities_board, is_invalid) = reduced_board.reduce();
-        if is_invalid {
-            NextBoardStates::Single(None)
-        } else if reduced_board.is_solution() || &reduced_board != self {
-            NextBoardStates::Single(Some(reduced_board))
-        } else {
-            NextBoardStates::States(
-                SudokuBoard::iter_positions()
-                    .filter({
-                        let reduced_board = reduced_board.clone();
-                        move |&pos| reduced_board[pos].is_none()
-                    })
-                    .flat_map(move |pos| {
-                        possibilities_board[pos]
-                            .clone()
-                            .iter()
-                            .map({
-                                let reduced_board = reduced_board.clone();
-                                move |space| {
-                                    let mut new_board = reduced_board.clone();
-                                    new_board[pos] = Some(space);
-                                    new_board
-                                }
-                            })
-                            .collect::<Vec<_>>()
-                    }),
-            )
+                Err(err) => err,
+            },
+            _ => match line.trim().parse::<SudokuBoard>() {
+                Ok(puzzle) => {
+                    let grid = puzzle.to_grid_string();
+                    *board = Some(puzzle);
+                    grid
+                }
+                Err(_) => format!("Unknown command {command}; try help"),
+            },
+        },
+    };
+    ControlFlow::Continue(reply)
+}
+
+fn parse_pos(args: &[&str]) -> Result<Pos, String> {
+    let [x, y] = args else {
+        return Err(String::from("Expected a cell as x y"));
+    };
+    x.parse()
+        .ok()
+        .zip(y.parse().ok())
+        .and_then(|(x, y)| Pos::new(x, y))
+        .ok_or(format!("({x}, {y}) isn't a cell; x and y go from 0 to 8"))
+}
+
+fn format_pos((x, y): (usize, usize)) -> String {
+    format!("({x}, {y})")
+}
+
+fn describe(reason: HintReason) -> String {
+    match reason {
+        HintReason::OnlyCandidate => String::from("it's the only value left for that cell"),
+        HintReason::OnlyPlace(region) => {
+            let region = match region {
+                SudokuRegion::Row(i) => format!("row {i}"),
+                SudokuRegion::Column(i) => format!("column {i}"),
+                SudokuRegion::Square(i) => format!("square {i}"),
+                SudokuRegion::Diagonal(i) => format!("diagonal {i}"),
+                SudokuRegion::Window(i) => format!("window {i}"),
+            };
+            format!("it's the only place left for it in {region}")
         }
     }
 }
 
-impl SolutionIdentifiable for SudokuBoard {
-    fn is_solution(&self) -> bool {
-        self.iter().all(|space| space.is_some())
-    }
-}
-
-impl Scoreable for SudokuBoard {
-    type Score = usize;
-
-    fn score(&self) -> Self::Score {
-        self.iter().filter(|space| space.is_none()).count()
-    }
-}
-
-#[test]
-fn test_reduction() {
-    #[rustfmt::skip]
-    let board_str = 
-"53  7    
-6  195   
- 98    6 
-8   6   3
-4  8 3  1
-7   2   6
- 6    28 
-   419  5
-    8  79";
-    let mut board: SudokuBoard = board_str.parse().unwrap();
-    println!("initial board:");
-    println!("{}", board);
-    board.reduce();
-    println!("after reduction:");
-    println!("{}", board);
-    assert_eq!(board.validate(), Ok(()));
-}
-
-#[test]
-fn test_solve_hard() {
-    use space_search::{search::*, *};
-    #[rustfmt::skip]
-    let board_str = 
-"2  5 74 6
-    31   
-      23 
-    2    
-86 31    
- 45      
-  9   7  
-  695   2
-  1  6  8";
-    let board: SudokuBoard = board_str.parse().unwrap();
-    println!("initial board:");
-    println!("{}", board);
-    let mut searcher: Searcher<guided::no_route::hashable::Manager<_>, _> = Searcher::new(board);
-    let solution = searcher.next().expect("Sudoku board has a solution");
-    println!("solution:");
-    println!("{}", solution);
-    assert_eq!(solution.validate(), Ok(()));
-}
-
-#[test]
-fn test_solve_hard_2() {
-    use space_search::{search::*, *};
-    #[rustfmt::skip]
-    let board_str = 
-"  65     
-7 5  23  
- 3     8 
- 5  96 7 
-1 4     8
-   82    
- 2     9 
-  72  4  
-     75  ";
-    let board: SudokuBoard = board_str.parse().unwrap();
-    println!("initial board:");
-    println!("{}", board);
-    let mut searcher: Searcher<guided::no_route::hashable::Manager<_>, _> = Searcher::new(board);
-    let solution = searcher.next().expect("Sudoku board has a solution");
-    println!("solution:");
-    println!("{}", solution);
-    assert_eq!(solution.validate(), Ok(()));
-}
-
-#[test]
-fn test_solve_hard_3() {
-    use space_search::{search::*, *};
-    #[rustfmt::skip]
-    let board_str = 
-" 293 8456
-5782 61 9
-   1 5 7 
-3 5 2 6  
-     9 4 
- 91 67   
- 3  5    
-     29 3
-9 7    24";
-    let board: SudokuBoard = board_str.parse().unwrap();
-    println!("initial board:");
-    println!("{}", board);
-    let mut searcher: Searcher<guided::no_route::hashable::Manager<_>, _> = Searcher::new(board);
-    let solution = searcher.next().expect("Sudoku board has a solution");
-    println!("solution:");
-    println!("{}", solution);
-    assert_eq!(solution.validate(), Ok(()));
-}
-
-#[test]
-fn test_solve_hard_4() {
-    use space_search::{search::*, *};
-    #[rustfmt::skip]
-    let board_str = 
-"5 8427   
- 4  1 7  
-19   3  2
-    6   5
-7     2  
-6 513 9  
-9    15  
-    4  2 
- 7      8";
-    let board: SudokuBoard = board_str.parse().unwrap();
-    println!("initial board:");
-    println!("{}", board);
-    let mut searcher: Searcher<guided::no_route::hashable::Manager<_>, _> = Searcher::new(board);
-    let solution = searcher.next().expect("Sudoku board has a solution");
-    println!("solution:");
-    println!("{}", solution);
-    assert_eq!(solution.validate(), Ok(()));
-}
-
-#[test]
-fn test_solo_candidate_deduction() {
-    #[rustfmt::skip]
-    let board_str = 
-"         
-3        
-6        
-2        
-1        
-     4   
-8        
-5        
-       4 ";
-    println!("{}", board_str.len());
-    let mut board: SudokuBoard = board_str.parse().unwrap();
-    println!("initial board:");
-    println!("{}", board);
-    board.reduce();
-    println!("solution:");
-    println!("{}", board);
-    assert_eq!(board.validate(), Ok(()));
-    assert_eq!(board[(0, 0)], Some(Space::Four));
-}
-
 #[test]
-fn test_reduction_2() {
-    let mut board: SudokuBoard = "2  5974 6
-6 4231   
-   8  23 
-    2    
-86231    
- 45    2 
-4 918276 
-786953142
- 21  6  8"
-        .parse()
-        .unwrap();
-    board.reduce();
-    let solution_board: SudokuBoard = "238597416
-694231857
-517864239
-173429685
-862315974
-945678321
-459182763
-786953142
-321746598"
-        .parse()
-        .unwrap();
-    assert_eq!(board, solution_board);
+fn test_parse_args() {
+    let parse = |args: &[&str]| parse_args(args.iter().map(|arg| arg.to_string()));
+    assert_eq!(
+        parse(&["puzzles.txt"]),
+        Ok(Command::Solve {
+            path: String::from("puzzles.txt"),
+            format: Format::Line
+        })
+    );
+    assert_eq!(
+        parse(&["--format=grid", "-"]),
+        Ok(Command::Solve {
+            path: String::from("-"),
+            format: Format::Grid
+        })
+    );
+    assert_eq!(parse(&["repl"]), Ok(Command::Repl));
+    assert_eq!(parse(&[]), Err(String::from("No input given")));
+    assert_eq!(
+        parse(&["--format=json", "-"]),
+        Err(String::from("Unknown option --format=json"))
+    );
+    assert_eq!(
+        parse(&["a.txt", "b.txt"]),
+        Err(String::from("Only one input can be given"))
+    );
 }
 
 #[test]
-fn test_manual_solve() {
-    #[rustfmt::skip]
-    let board_str = 
-"2  5 74 6
-    31   
-      23 
-    2    
-86 31    
- 45      
-  9   7  
-  695   2
-  1  6  8";
-    let mut board: SudokuBoard = board_str.parse().unwrap();
-    println!("\n{board}");
-    board.reduce();
-    println!("\n{board}");
-    println!("next moves: {}", board.next_states().count());
-    let before_adjustment = board.clone();
-    board[(3, 2)] = Some(Space::Eight);
-    println!("\n{board}");
-    assert!(before_adjustment
-        .next_states()
-        .find(|b| b == &board)
-        .is_some());
-    board.reduce();
-    println!("\n{board}");
-    assert_eq!(board.validate(), Ok(()));
-    assert!(board.is_solution());
-}
-fn main() {
-    use space_search::{search::*, *};
-    #[rustfmt::skip]
-    let board_str = 
-"2  5 74 6
-    31   
-      23 
-    2    
-86 31    
- 45      
-  9   7  
-  695   2
-  1  6  8";
-    let board: SudokuBoard = board_str.parse().unwrap();
-    println!("initial board:");
-    println!("{}", board);
-    let mut searcher: Searcher<guided::route::hashable::Manager<_>, _> = Searcher::new(board);
-    let solution = searcher.next().expect("Sudoku board has a solution");
-    println!("solution:");
-    for board in solution {
-        println!("---------\n{}", board);
-    }
+fn test_execute() {
+    let mut board = None;
+    let reply = |board: &mut Option<SudokuBoard>, line| match execute(board, line) {
+        ControlFlow::Continue(reply) => reply,
+        ControlFlow::Break(()) => panic!("{line} quit the REPL"),
+    };
+
+    assert_eq!(reply(&mut board, "hint"), "No puzzle loaded yet");
+    let puzzle =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+    reply(&mut board, puzzle);
+    assert_eq!(board, Some(puzzle.parse().unwrap()));
+
+    assert_eq!(reply(&mut board, "candidates 2 0"), "1 2 4");
+    assert_eq!(
+        reply(&mut board, "set 2 0 5"),
+        "Can't place Five at (2, 0): (0, 0) already holds it"
+    );
+    assert_eq!(
+        reply(&mut board, "set 9 0 5"),
+        "(9, 0) isn't a cell; x and y go from 0 to 8"
+    );
+    reply(&mut board, "set 2 0 4");
+    assert_eq!(board.as_ref().unwrap()[(2, 0)], Some(Space::Four));
+    reply(&mut board, "clear 2 0");
+    assert_eq!(board.as_ref().unwrap()[(2, 0)], None);
+
+    let hint = reply(&mut board, "hint");
+    reply(&mut board, "step");
+    assert_eq!(board.as_ref().unwrap().clue_count(), 31);
+    assert_ne!(reply(&mut board, "hint"), hint);
+
+    let solution = board.as_ref().unwrap().solve().unwrap();
+    assert_eq!(reply(&mut board, "solve"), solution.to_grid_string());
+    assert_eq!(
+        reply(&mut board, "frobnicate"),
+        "Unknown command frobnicate; try help"
+    );
+    assert_eq!(execute(&mut board, "quit"), ControlFlow::Break(()));
 }
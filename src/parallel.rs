@@ -0,0 +1,63 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use rayon::prelude::*;
+
+use crate::{techniques, NextBoardStates, Rules, Solver, SudokuBoard, ValueOrder};
+
+impl SudokuBoard {
+    /// Like [`SudokuBoard::solve`], but once reduction stalls, searches each
+    /// candidate of the first cell it has to guess at on its own thread. The
+    /// first solution found stops the other searches.
+    ///
+    /// On a board with several solutions, which one is returned depends on
+    /// which thread finishes first.
+    pub fn solve_parallel(&self) -> Option<SudokuBoard> {
+        let rules = Rules::classic();
+        let techniques = techniques::sound();
+        let mut board = self.clone();
+        let branches = loop {
+            match board.next_states_with(rules, &techniques, ValueOrder::Ascending) {
+                NextBoardStates::Single(None) => return None,
+                NextBoardStates::Single(Some(reduced)) if reduced.is_complete() => {
+                    return rules.is_solution(&reduced).then_some(reduced);
+                }
+                NextBoardStates::Single(Some(reduced)) => board = reduced,
+                NextBoardStates::States(branches) => break branches.collect::<Vec<_>>(),
+            }
+        };
+
+        let found = AtomicBool::new(false);
+        branches.into_par_iter().find_map_any(|branch| {
+            let solution = Solver::new().cancelled_by(&found).solve(&branch)?;
+            found.store(true, Ordering::Relaxed);
+            Some(solution)
+        })
+    }
+}
+
+#[test]
+fn test_solve_parallel() {
+    // the test_solve_hard boards
+    for board_str in [
+        "2..5.74.6....31.........23.....2....86.31.....45........9...7....695...2..1..6..8",
+        "..65.....7.5..23...3.....8..5..96.7.1.4.....8...82.....2.....9...72..4.......75..",
+        ".293.84565782.61.9...1.5.7.3.5.2.6.......9.4..91.67....3..5.........29.39.7....24",
+        "5.8427....4..1.7..19...3..2....6...57.....2..6.513.9..9....15......4..2..7......8",
+    ] {
+        let board: SudokuBoard = board_str.parse().unwrap();
+        assert_eq!(board.solve_parallel(), board.solve());
+    }
+
+    let solved = crate::easy_solution();
+    assert_eq!(solved.solve_parallel(), Some(solved.clone()));
+
+    let mut unsolvable = SudokuBoard::empty();
+    unsolvable[(0, 0)] = Some(crate::Space::One);
+    unsolvable[(8, 0)] = Some(crate::Space::One);
+    assert_eq!(unsolvable.solve_parallel(), None);
+
+    let solution = SudokuBoard::empty()
+        .solve_parallel()
+        .expect("an empty board has solutions");
+    assert!(Rules::classic().is_solution(&solution));
+}
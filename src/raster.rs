@@ -0,0 +1,127 @@
+use crate::{SudokuBoard, BOX_SIZE, SIDE};
+
+const GLYPH_WIDTH: usize = 5;
+const GLYPH_HEIGHT: usize = 7;
+
+/// A 5x7 bitmap of each digit from 1 to 9, one row per byte with the
+/// leftmost pixel in the highest of the low five bits.
+const GLYPHS: [[u8; GLYPH_HEIGHT]; SIDE] = [
+    [
+        0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110,
+    ],
+    [
+        0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111,
+    ],
+    [
+        0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110,
+    ],
+    [
+        0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010,
+    ],
+    [
+        0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110,
+    ],
+    [
+        0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110,
+    ],
+    [
+        0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000,
+    ],
+    [
+        0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110,
+    ],
+    [
+        0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100,
+    ],
+];
+
+const WHITE: u8 = 255;
+const BLACK: u8 = 0;
+
+impl SudokuBoard {
+    /// Renders the board as a greyscale PNG with cells `cell_px` pixels wide,
+    /// thick lines around each box, and empty cells left blank. The image is
+    /// `9 * cell_px + 1` pixels square, so the last cells have lines on their
+    /// far sides too.
+    ///
+    /// # Panics
+    ///
+    /// If `cell_px` is 0.
+    pub fn to_png(&self, cell_px: u32) -> Vec<u8> {
+        assert!(cell_px > 0, "cells must be at least a pixel wide");
+        let cell = cell_px as usize;
+        let size = SIDE * cell + 1;
+        let mut pixels = vec![WHITE; size * size];
+
+        for i in 0..=SIDE {
+            // box lines spread a pixel either side, where the image allows
+            let offset = i * cell;
+            let spread = if i % BOX_SIZE == 0 { 1 } else { 0 };
+            for line in offset.saturating_sub(spread)..=(offset + spread).min(size - 1) {
+                for along in 0..size {
+                    pixels[line * size + along] = BLACK;
+                    pixels[along * size + line] = BLACK;
+                }
+            }
+        }
+
+        let scale = (cell / 10).max(1);
+        for pos @ (x, y) in SudokuBoard::iter_positions() {
+            let Some(space) = self[pos] else {
+                continue;
+            };
+            let left = x * cell + cell.saturating_sub(GLYPH_WIDTH * scale).div_ceil(2);
+            let top = y * cell + cell.saturating_sub(GLYPH_HEIGHT * scale).div_ceil(2);
+            for (row, bits) in GLYPHS[space.idx()].iter().enumerate() {
+                for col in 0..GLYPH_WIDTH {
+                    if bits & (1 << (GLYPH_WIDTH - 1 - col)) == 0 {
+                        continue;
+                    }
+                    for dy in 0..scale {
+                        for dx in 0..scale {
+                            let (px, py) = (left + col * scale + dx, top + row * scale + dy);
+                            if px < size && py < size {
+                                pixels[py * size + px] = BLACK;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut png = Vec::new();
+        let mut encoder = png::Encoder::new(&mut png, size as u32, size as u32);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&pixels))
+            .expect("writing to a Vec can't fail");
+        png
+    }
+}
+
+#[test]
+fn test_to_png() {
+    let board = crate::easy_puzzle();
+    let png = board.to_png(20);
+    assert_eq!(
+        png[..8],
+        [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n']
+    );
+
+    let mut reader = png::Decoder::new(png.as_slice()).read_info().unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut pixels).unwrap();
+    assert_eq!((info.width, info.height), (181, 181));
+    let pixel = |x: usize, y: usize| pixels[y * 181 + x];
+
+    // borders, then the middle of an empty cell and of the 5 at (0, 0)
+    assert_eq!(pixel(0, 90), BLACK);
+    assert_eq!(pixel(60, 90), BLACK);
+    assert_eq!(pixel(30, 30), WHITE);
+    assert!((2..18).any(|y| (2..18).any(|x| pixel(x, y) == BLACK)));
+    assert!((22..38).all(|y| (22..38).all(|x| pixel(x, y) == WHITE)));
+
+    assert_ne!(SudokuBoard::empty().to_png(20), png);
+}
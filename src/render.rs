@@ -0,0 +1,204 @@
+use std::fmt::Write;
+
+use crate::{SudokuBoard, BOX_SIZE, SIDE};
+
+/// Width and height of a cell in SVG user units.
+const CELL_SIZE: usize = 40;
+const GRID_SIZE: usize = CELL_SIZE * SIDE;
+/// Room around the grid so the outer border isn't clipped.
+const MARGIN: usize = 2;
+
+impl SudokuBoard {
+    /// Renders the board as a self-contained SVG image, with thick lines
+    /// around each box and empty cells left blank.
+    pub fn to_svg(&self) -> String {
+        self.svg(None)
+    }
+
+    /// Like [`SudokuBoard::to_svg`], with the clues from `givens` in bold and
+    /// every other filled cell in a normal weight.
+    pub fn to_svg_with_givens(&self, givens: &SudokuBoard) -> String {
+        self.svg(Some(givens))
+    }
+
+    fn svg(&self, givens: Option<&SudokuBoard>) -> String {
+        let size = GRID_SIZE + 2 * MARGIN;
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}" viewBox="-{MARGIN} -{MARGIN} {size} {size}">"#
+        );
+        write!(
+            svg,
+            r#"<rect x="0" y="0" width="{GRID_SIZE}" height="{GRID_SIZE}" fill="white"/>"#
+        )
+        .unwrap();
+
+        for i in 0..=SIDE {
+            let offset = i * CELL_SIZE;
+            let width = if i % BOX_SIZE == 0 { 3 } else { 1 };
+            write!(
+                svg,
+                r#"<line x1="{offset}" y1="0" x2="{offset}" y2="{GRID_SIZE}" stroke="black" stroke-width="{width}" stroke-linecap="square"/>"#
+            )
+            .unwrap();
+            write!(
+                svg,
+                r#"<line x1="0" y1="{offset}" x2="{GRID_SIZE}" y2="{offset}" stroke="black" stroke-width="{width}" stroke-linecap="square"/>"#
+            )
+            .unwrap();
+        }
+
+        for pos @ (x, y) in SudokuBoard::iter_positions() {
+            let Some(space) = self[pos] else {
+                continue;
+            };
+            let weight = match givens {
+                Some(givens) if givens[pos].is_none() => "normal",
+                _ => "bold",
+            };
+            let (cx, cy) = (x * CELL_SIZE + CELL_SIZE / 2, y * CELL_SIZE + CELL_SIZE / 2);
+            write!(
+                svg,
+                r#"<text x="{cx}" y="{cy}" text-anchor="middle" dominant-baseline="central" font-family="sans-serif" font-size="24" font-weight="{weight}">{}</text>"#,
+                usize::from(space)
+            )
+            .unwrap();
+        }
+
+        svg.push_str("</svg>");
+        svg
+    }
+
+    /// Renders the board as an HTML `<table>` for styling with CSS. The table
+    /// has class `sudoku`, cells starting a box on the left or top have
+    /// `box-left` or `box-top`, and filled cells are marked `given`. Empty
+    /// cells are left as empty `<td>`s.
+    pub fn to_html(&self) -> String {
+        self.html(None)
+    }
+
+    /// Like [`SudokuBoard::to_html`], with the clues from `givens` marked
+    /// `given` and every other filled cell marked `filled`.
+    pub fn to_html_with_givens(&self, givens: &SudokuBoard) -> String {
+        self.html(Some(givens))
+    }
+
+    fn html(&self, givens: Option<&SudokuBoard>) -> String {
+        let mut html = String::from(r#"<table class="sudoku">"#);
+        for y in 0..SIDE {
+            html.push_str("<tr>");
+            for x in 0..SIDE {
+                let mut classes = Vec::new();
+                if x % BOX_SIZE == 0 {
+                    classes.push("box-left");
+                }
+                if y % BOX_SIZE == 0 {
+                    classes.push("box-top");
+                }
+                let space = self[(x, y)];
+                match (space, givens) {
+                    (None, _) => {}
+                    (Some(_), Some(givens)) if givens[(x, y)].is_none() => classes.push("filled"),
+                    (Some(_), _) => classes.push("given"),
+                }
+                match classes.is_empty() {
+                    true => html.push_str("<td>"),
+                    false => write!(html, r#"<td class="{}">"#, classes.join(" ")).unwrap(),
+                }
+                if let Some(space) = space {
+                    write!(html, "{}", usize::from(space)).unwrap();
+                }
+                html.push_str("</td>");
+            }
+            html.push_str("</tr>");
+        }
+        html.push_str("</table>");
+        html
+    }
+}
+
+/// Checks that every tag in `xml` is closed in order, returning the text
+/// content of each element that has any.
+#[cfg(test)]
+fn parse_xml(xml: &str) -> Result<Vec<String>, String> {
+    let mut open = Vec::new();
+    let mut texts = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find('<') {
+        let text = &rest[..start];
+        if !text.trim().is_empty() {
+            texts.push(text.to_string());
+        }
+        let end = rest[start..].find('>').ok_or("unterminated tag")? + start;
+        let tag = &rest[start + 1..end];
+        if let Some(name) = tag.strip_prefix('/') {
+            if open.pop() != Some(name) {
+                return Err(format!("unexpected closing tag {name}"));
+            }
+        } else if !tag.ends_with('/') {
+            open.push(tag.split_whitespace().next().ok_or("empty tag")?);
+        }
+        rest = &rest[end + 1..];
+        if open.is_empty() && !rest.trim().is_empty() {
+            return Err(String::from("content after the root element"));
+        }
+    }
+    match open.is_empty() {
+        true => Ok(texts),
+        false => Err(format!("unclosed tags {open:?}")),
+    }
+}
+
+#[test]
+fn test_to_svg() {
+    let board = crate::easy_puzzle();
+    let svg = board.to_svg();
+    assert!(svg.starts_with("<svg "));
+    let digits = parse_xml(&svg).expect("SVG is well formed");
+    assert_eq!(digits.len(), board.clue_count());
+    let expected = board
+        .iter()
+        .flatten()
+        .map(|&space| usize::from(space).to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(digits, expected);
+    assert_eq!(svg.matches("<line ").count(), 2 * (SIDE + 1));
+    assert_eq!(svg.matches(r#"stroke-width="3""#).count(), 8);
+
+    let solution = board.solve().expect("Sudoku board has a solution");
+    let svg = solution.to_svg_with_givens(&board);
+    assert_eq!(parse_xml(&svg).map(|digits| digits.len()), Ok(81));
+    assert_eq!(
+        svg.matches(r#"font-weight="bold""#).count(),
+        board.clue_count()
+    );
+    assert_eq!(
+        svg.matches(r#"font-weight="normal""#).count(),
+        board.empty_count()
+    );
+    assert_eq!(
+        parse_xml("<svg><text>1</svg></text>"),
+        Err(String::from("unexpected closing tag svg"))
+    );
+}
+
+#[test]
+fn test_to_html() {
+    let board = crate::easy_puzzle();
+    let html = board.to_html();
+    assert!(
+        html.starts_with(r#"<table class="sudoku"><tr><td class="box-left box-top given">5</td>"#)
+    );
+    assert_eq!(html.matches("<td").count(), 81);
+    assert_eq!(html.matches("<tr>").count(), SIDE);
+    assert_eq!(html.matches("box-left").count(), 27);
+    assert_eq!(html.matches("box-top").count(), 27);
+    let digits = parse_xml(&html).expect("HTML is well formed");
+    assert_eq!(digits.len(), board.clue_count());
+    assert_eq!(html.matches("given").count(), board.clue_count());
+
+    let solution = board.solve().expect("Sudoku board has a solution");
+    let html = solution.to_html_with_givens(&board);
+    assert_eq!(parse_xml(&html).map(|digits| digits.len()), Ok(81));
+    assert_eq!(html.matches("given").count(), board.clue_count());
+    assert_eq!(html.matches("filled").count(), board.empty_count());
+}
@@ -0,0 +1,778 @@
+use std::{array, sync::OnceLock};
+
+use crate::{
+    Board, BoardPosition, PossibilitySpaceBoard, Space, SudokuBoard, SudokuChoices, SudokuRegion,
+    SudokuRegion::*, CELLS, SIDE,
+};
+
+/// A variant rule layered on top of classic sudoku.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Constraint {
+    /// Both main diagonals must also hold every value once.
+    Diagonal,
+    /// Four extra 3x3 windows, each one cell in from a corner square, must
+    /// also hold every value once, as in hyper sudoku.
+    Windoku,
+    /// Cells a chess knight's move apart can't hold the same value.
+    AntiKnight,
+    /// Irregular regions take the place of the squares. The map gives the
+    /// region id, `0..SIDE`, of each cell in reading order.
+    Jigsaw(Box<[usize; CELLS]>),
+    /// A killer sudoku cage.
+    Cage(Cage),
+    /// Cells marked to hold only even or only odd values, in reading order.
+    Parity(Box<[Option<Parity>; CELLS]>),
+}
+
+impl Constraint {
+    /// The region map of classic sudoku, where each region is a square.
+    pub fn square_map() -> [usize; CELLS] {
+        array::from_fn(|i| match SudokuRegion::square_of((i % SIDE, i / SIDE)) {
+            Square(square) => square,
+            _ => unreachable!(),
+        })
+    }
+}
+
+/// Whether a cell under [`Constraint::Parity`] must hold an even or an odd
+/// value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Parity {
+    Even,
+    Odd,
+}
+
+impl Parity {
+    pub fn allows(self, space: Space) -> bool {
+        (usize::from(space) % 2 == 0) == (self == Parity::Even)
+    }
+}
+
+/// A group of cells that must hold distinct values adding up to `sum`. The
+/// cells must be on the board and listed once each.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cage {
+    pub cells: Vec<BoardPosition>,
+    pub sum: usize,
+}
+
+impl Cage {
+    /// For each cell, the values among `candidates` that appear in some way of
+    /// filling the whole cage with distinct values from `candidates` that adds
+    /// up to the sum.
+    fn supported(&self, candidates: &[SudokuChoices]) -> Vec<SudokuChoices> {
+        struct Walk<'a> {
+            candidates: &'a [SudokuChoices],
+            sum: usize,
+            /// Whether the cells from an index on can be completed given the
+            /// values used so far, which also fix the running total.
+            feasible: Vec<Option<bool>>,
+            supported: Vec<SudokuChoices>,
+        }
+
+        impl Walk<'_> {
+            fn visit(&mut self, i: usize, used: u16, total: usize) -> bool {
+                if i == self.candidates.len() {
+                    return total == self.sum;
+                }
+                let key = (i << SIDE) | usize::from(used);
+                if let Some(feasible) = self.feasible[key] {
+                    return feasible;
+                }
+                let mut feasible = false;
+                for space in self.candidates[i].iter() {
+                    let bit = 1 << space.idx();
+                    let total = total + usize::from(space);
+                    if used & bit == 0 && total <= self.sum && self.visit(i + 1, used | bit, total)
+                    {
+                        self.supported[i].set(space, true);
+                        feasible = true;
+                    }
+                }
+                self.feasible[key] = Some(feasible);
+                feasible
+            }
+        }
+
+        let mut walk = Walk {
+            candidates,
+            sum: self.sum,
+            feasible: vec![None; (candidates.len() + 1) << SIDE],
+            supported: vec![SudokuChoices::none(); candidates.len()],
+        };
+        walk.visit(0, 0, 0);
+        walk.supported
+    }
+}
+
+const KNIGHT_MOVES: [(isize, isize); 8] = [
+    (1, 2),
+    (2, 1),
+    (2, -1),
+    (1, -2),
+    (-1, -2),
+    (-2, -1),
+    (-2, 1),
+    (-1, 2),
+];
+
+/// Every cell a knight's move away from `pos`.
+fn knight_moves((x, y): BoardPosition) -> impl Iterator<Item = BoardPosition> {
+    KNIGHT_MOVES.into_iter().filter_map(move |(dx, dy)| {
+        let x = x.checked_add_signed(dx).filter(|&x| x < SIDE)?;
+        let y = y.checked_add_signed(dy).filter(|&y| y < SIDE)?;
+        Some((x, y))
+    })
+}
+
+/// The regions a board's values must be spread across, classic rows,
+/// columns and squares plus whatever [`Constraint`]s are added on top.
+#[derive(Clone, Debug)]
+pub struct Rules {
+    constraints: Vec<Constraint>,
+    houses: Vec<(SudokuRegion, Vec<BoardPosition>)>,
+    houses_of: Board<Vec<usize>>,
+    peers: Board<Vec<BoardPosition>>,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules::new([])
+    }
+}
+
+impl Rules {
+    /// # Panics
+    ///
+    /// If a [`Constraint::Jigsaw`] map doesn't split the board into `SIDE`
+    /// regions of `SIDE` cells each, or a [`Cage`] has a cell off the board
+    /// or the same cell twice.
+    pub fn new(constraints: impl IntoIterator<Item = Constraint>) -> Self {
+        let constraints = constraints.into_iter().collect::<Vec<_>>();
+
+        let mut regions = SudokuRegion::all().collect::<Vec<_>>();
+        for constraint in &constraints {
+            match constraint {
+                Constraint::Diagonal => regions.extend([Diagonal(0), Diagonal(1)]),
+                Constraint::Windoku => regions.extend((0..4).map(Window)),
+                Constraint::AntiKnight
+                | Constraint::Jigsaw(_)
+                | Constraint::Cage(_)
+                | Constraint::Parity(_) => {}
+            }
+        }
+
+        let jigsaw = constraints.iter().find_map(|constraint| match constraint {
+            Constraint::Jigsaw(map) => Some(map),
+            _ => None,
+        });
+        if let Some(map) = jigsaw {
+            assert!(
+                (0..SIDE).all(|id| map.iter().filter(|&&region| region == id).count() == SIDE),
+                "jigsaw map must have {SIDE} regions of {SIDE} cells"
+            );
+        }
+        for constraint in &constraints {
+            let Constraint::Cage(cage) = constraint else {
+                continue;
+            };
+            for (i, &(x, y)) in cage.cells.iter().enumerate() {
+                assert!(
+                    x < SIDE && y < SIDE,
+                    "cage cell ({x}, {y}) is off the board"
+                );
+                assert!(
+                    !cage.cells[..i].contains(&(x, y)),
+                    "cage lists ({x}, {y}) more than once"
+                );
+            }
+        }
+        let houses = regions
+            .into_iter()
+            .map(|region| {
+                let cells: Vec<_> = match (region, jigsaw) {
+                    (Square(id), Some(map)) => SudokuBoard::iter_positions()
+                        .filter(|&(x, y)| map[y * SIDE + x] == id)
+                        .collect(),
+                    _ => region.into_iter().collect(),
+                };
+                (region, cells)
+            })
+            .collect::<Vec<_>>();
+
+        // the houses of each cell, in row, column, square order, then the rest
+        let houses_of = Board::from_fn(|pos| {
+            let mut indices = (0..houses.len())
+                .filter(|&h| houses[h].1.contains(&pos))
+                .collect::<Vec<_>>();
+            indices.sort_by_key(|&h| match houses[h].0 {
+                Row(_) => 0,
+                Column(_) => 1,
+                Square(_) => 2,
+                Diagonal(_) | Window(_) => 3,
+            });
+            indices
+        });
+
+        let peers = Board::from_fn(|pos| {
+            let mut peers = Vec::new();
+            for &h in &houses_of[pos] {
+                for &other in &houses[h].1 {
+                    if other != pos && !peers.contains(&other) {
+                        peers.push(other);
+                    }
+                }
+            }
+            if constraints.contains(&Constraint::AntiKnight) {
+                for other in knight_moves(pos) {
+                    if !peers.contains(&other) {
+                        peers.push(other);
+                    }
+                }
+            }
+            for constraint in &constraints {
+                let Constraint::Cage(cage) = constraint else {
+                    continue;
+                };
+                if cage.cells.contains(&pos) {
+                    for &other in &cage.cells {
+                        if other != pos && !peers.contains(&other) {
+                            peers.push(other);
+                        }
+                    }
+                }
+            }
+            peers
+        });
+
+        Rules {
+            constraints,
+            houses,
+            houses_of,
+            peers,
+        }
+    }
+
+    /// Rules for classic sudoku, shared so hot paths don't rebuild them.
+    pub(crate) fn classic() -> &'static Rules {
+        static CLASSIC: OnceLock<Rules> = OnceLock::new();
+        CLASSIC.get_or_init(Rules::default)
+    }
+
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// Every killer cage among the constraints.
+    pub fn cages(&self) -> impl Iterator<Item = &Cage> {
+        self.constraints
+            .iter()
+            .filter_map(|constraint| match constraint {
+                Constraint::Cage(cage) => Some(cage),
+                _ => None,
+            })
+    }
+
+    /// Removes every candidate that can't be part of a cage adding up to its
+    /// sum, returning whether any were removed.
+    pub(crate) fn prune_cages(&self, possibilities_board: &mut PossibilitySpaceBoard) -> bool {
+        let mut adjusted = false;
+        for cage in self.cages() {
+            let candidates = cage
+                .cells
+                .iter()
+                .map(|&pos| possibilities_board[pos])
+                .collect::<Vec<_>>();
+            for (&pos, supported) in cage.cells.iter().zip(cage.supported(&candidates)) {
+                adjusted |= possibilities_board[pos] != supported;
+                possibilities_board[pos] = supported;
+            }
+        }
+        adjusted
+    }
+
+    /// Every cell marked by a [`Constraint::Parity`], with its parity.
+    fn parities(&self) -> impl Iterator<Item = (BoardPosition, Parity)> + '_ {
+        self.constraints
+            .iter()
+            .filter_map(|constraint| match constraint {
+                Constraint::Parity(map) => Some(map),
+                _ => None,
+            })
+            .flat_map(|map| {
+                SudokuBoard::iter_positions()
+                    .filter_map(|(x, y)| map[y * SIDE + x].map(|parity| ((x, y), parity)))
+            })
+    }
+
+    /// Removes every candidate of the wrong parity from marked cells.
+    pub(crate) fn prune_parities(&self, possibilities_board: &mut PossibilitySpaceBoard) {
+        for (pos, parity) in self.parities() {
+            for space in Space::all() {
+                if !parity.allows(space) {
+                    possibilities_board[pos].set(space, false);
+                }
+            }
+        }
+    }
+
+    /// The first marked cell holding a value of the wrong parity.
+    fn broken_parity(&self, board: &SudokuBoard) -> Option<(BoardPosition, Space, Parity)> {
+        self.parities().find_map(|(pos, parity)| {
+            let space = board[pos]?;
+            (!parity.allows(space)).then_some((pos, space, parity))
+        })
+    }
+
+    /// The first cage whose values already add up to more than its sum, or
+    /// to anything else once it is full, along with that total.
+    fn broken_cage(&self, board: &SudokuBoard) -> Option<(&Cage, usize)> {
+        self.cages().find_map(|cage| {
+            let values = cage.cells.iter().filter_map(|&pos| board[pos]);
+            let total = values.clone().map(usize::from).sum::<usize>();
+            let full = values.count() == cage.cells.len();
+            (total > cage.sum || (full && total != cage.sum)).then_some((cage, total))
+        })
+    }
+
+    /// Every region that must hold each value once, with its cells.
+    pub fn houses(&self) -> impl Iterator<Item = (SudokuRegion, &[BoardPosition])> {
+        self.houses
+            .iter()
+            .map(|(region, cells)| (*region, cells.as_slice()))
+    }
+
+    /// The regions containing `pos`: its row, column and square first.
+    pub(crate) fn houses_of(
+        &self,
+        pos: BoardPosition,
+    ) -> impl Iterator<Item = (SudokuRegion, &[BoardPosition])> {
+        self.houses_of[pos].iter().map(|&h| {
+            let (region, cells) = &self.houses[h];
+            (*region, cells.as_slice())
+        })
+    }
+
+    /// Every other cell that can't share a value with `pos`.
+    pub fn peers(&self, pos: BoardPosition) -> &[BoardPosition] {
+        &self.peers[pos]
+    }
+
+    /// Every pair of peers that hold the same value, those sharing a region
+    /// first. Cage sums aren't checked here.
+    pub fn conflicts(&self, board: &SudokuBoard) -> Vec<(Space, BoardPosition, BoardPosition)> {
+        let mut conflicts = Vec::new();
+        for (_, cells) in self.houses() {
+            let filled = cells
+                .iter()
+                .filter_map(|&pos| board[pos].map(|space| (pos, space)))
+                .collect::<Vec<_>>();
+            for (i, &(a, space)) in filled.iter().enumerate() {
+                for &(b, other) in &filled[i + 1..] {
+                    let conflict = (space, a, b);
+                    if space == other && !conflicts.contains(&conflict) {
+                        conflicts.push(conflict);
+                    }
+                }
+            }
+        }
+
+        // peers that share no region, like a knight's move apart
+        for (a, space) in board.iter_filled() {
+            for &b in self.peers(a) {
+                let index = |(x, y): BoardPosition| y * SIDE + x;
+                if index(b) > index(a)
+                    && board[b] == Some(space)
+                    && !conflicts.contains(&(space, a, b))
+                    && !conflicts.contains(&(space, b, a))
+                {
+                    conflicts.push((space, a, b));
+                }
+            }
+        }
+        conflicts
+    }
+
+    pub fn validate(&self, board: &SudokuBoard) -> Result<(), String> {
+        let Some(&(space, a, b)) = self.conflicts(board).first() else {
+            return match self.broken_cage(board) {
+                Some((cage, total)) => Err(format!(
+                    "Cage at {:?} adds up to {total}, not {}",
+                    cage.cells[0], cage.sum
+                )),
+                None => match self.broken_parity(board) {
+                    Some((pos, space, parity)) => Err(format!(
+                        "{pos:?} holds {space:?}, but must be {}",
+                        match parity {
+                            Parity::Even => "even",
+                            Parity::Odd => "odd",
+                        }
+                    )),
+                    None => Ok(()),
+                },
+            };
+        };
+        let Some((region, _)) = self
+            .houses()
+            .find(|(_, cells)| cells.contains(&a) && cells.contains(&b))
+        else {
+            if self
+                .cages()
+                .any(|cage| cage.cells.contains(&a) && cage.cells.contains(&b))
+            {
+                return Err(format!("{a:?} and {b:?} share a cage: duplicate {space:?}"));
+            }
+            return Err(format!(
+                "{a:?} and {b:?} are a knight's move apart: duplicate {space:?}"
+            ));
+        };
+        let region = match region {
+            Row(i) => format!("Row {i}"),
+            Column(i) => format!("Column {i}"),
+            Square(i) => format!("Square {i}"),
+            Diagonal(i) => format!("Diagonal {i}"),
+            Window(i) => format!("Window {i}"),
+        };
+        Err(format!("{region} is invalid: duplicate {space:?}"))
+    }
+
+    /// Whether `board` is complete and breaks none of these rules.
+    pub fn is_solution(&self, board: &SudokuBoard) -> bool {
+        board.is_complete()
+            && self.conflicts(board).is_empty()
+            && self.broken_cage(board).is_none()
+            && self.broken_parity(board).is_none()
+    }
+}
+
+#[test]
+fn test_diagonal() {
+    use crate::Solver;
+
+    let board: SudokuBoard =
+        "6......4....3.............5..74...818..1.....3...2.4......8......1.9.83........5."
+            .parse()
+            .unwrap();
+    let rules = Rules::new([Constraint::Diagonal]);
+    assert_eq!(rules.peers((4, 4)).len(), 20 + 12);
+
+    let solution = Solver::new()
+        .with_constraint(Constraint::Diagonal)
+        .solve(&board)
+        .expect("Sudoku board has a solution");
+    assert_eq!(rules.validate(&solution), Ok(()));
+    assert!(rules.is_solution(&solution));
+    assert_eq!(
+        solution.to_string(),
+        "639251748\n458367912\n172849365\n967435281\n824176593\n315928476\n796583124\n541692837\n283714659"
+    );
+
+    for diagonal in [Diagonal(0), Diagonal(1)] {
+        let mut values = diagonal
+            .into_iter()
+            .map(|pos| solution[pos])
+            .collect::<Vec<_>>();
+        values.sort_by_key(|space| space.map(usize::from));
+        values.dedup();
+        assert_eq!(values.len(), SIDE);
+    }
+
+    // without the diagonal rule the givens are ambiguous
+    assert_eq!(board.count_solutions(2), 2);
+}
+
+#[test]
+fn test_windoku() {
+    use crate::Solver;
+
+    let board: SudokuBoard =
+        ".2.8...3...1..7....7.........4.....18........26.....93.........5..47...6.....8..."
+            .parse()
+            .unwrap();
+    let rules = Rules::new([Constraint::Windoku]);
+    assert_eq!(
+        rules.houses().count(),
+        Rules::classic().houses().count() + 4
+    );
+    // (2, 2) shares a window with (3, 3), but no row, column or square
+    assert!(rules.peers((2, 2)).contains(&(3, 3)));
+    assert!(!Rules::classic().peers((2, 2)).contains(&(3, 3)));
+
+    let solver = Solver::new().with_constraint(Constraint::Windoku);
+    assert_eq!(solver.count_solutions(&board, 2), 1);
+    let solution = solver.solve(&board).expect("Sudoku board has a solution");
+    assert_eq!(rules.validate(&solution), Ok(()));
+    assert!(rules.is_solution(&solution));
+    assert_eq!(
+        solution.to_string(),
+        "426819537\n351247968\n978653412\n734965821\n819732645\n265184793\n187396254\n592471386\n643528179"
+    );
+
+    for window in (0..4).map(Window) {
+        let mut values = window
+            .into_iter()
+            .map(|pos| solution[pos])
+            .collect::<Vec<_>>();
+        values.sort_by_key(|space| space.map(usize::from));
+        values.dedup();
+        assert_eq!(values.len(), SIDE);
+    }
+
+    let mut clash = board.clone();
+    clash[(3, 3)] = Some(Space::Seven);
+    assert_eq!(clash.validate(), Ok(()));
+    assert_eq!(
+        rules.validate(&clash),
+        Err(String::from("Window 0 is invalid: duplicate Seven"))
+    );
+
+    // without the windows the givens are ambiguous
+    assert_eq!(board.count_solutions(2), 2);
+}
+
+#[test]
+fn test_anti_knight() {
+    use crate::Solver;
+
+    let board: SudokuBoard =
+        "25...7.....7......91.....3.....5.3.4.4......96.3..95....5.....2.7.8....5.62....7."
+            .parse()
+            .unwrap();
+    let rules = Rules::new([Constraint::AntiKnight]);
+    assert_eq!(rules.peers((4, 4)).len(), 20 + 8);
+    assert_eq!(rules.peers((0, 0)).len(), 20);
+
+    let solver = Solver::new().with_constraint(Constraint::AntiKnight);
+    assert_eq!(solver.count_solutions(&board, 2), 1);
+    let solution = solver.solve(&board).expect("Sudoku board has a solution");
+    assert!(rules.is_solution(&solution));
+    assert_eq!(
+        solution.to_string(),
+        "254367198\n387291456\n916584237\n791658324\n548123769\n623749581\n835476912\n179832645\n462915873"
+    );
+    for pos in SudokuBoard::iter_positions() {
+        for other in knight_moves(pos) {
+            assert_ne!(solution[pos], solution[other]);
+        }
+    }
+
+    // a knight's move clash is caught even though no region is shared
+    let mut clash = board.clone();
+    clash[(6, 2)] = Some(Space::Seven);
+    assert_eq!(clash.validate(), Ok(()));
+    assert_eq!(
+        rules.conflicts(&clash),
+        vec![(Space::Seven, (5, 0), (6, 2))]
+    );
+    assert_eq!(
+        rules.validate(&clash),
+        Err(String::from(
+            "(5, 0) and (6, 2) are a knight's move apart: duplicate Seven"
+        ))
+    );
+}
+
+#[test]
+fn test_jigsaw() {
+    use crate::Solver;
+
+    assert_eq!(
+        Rules::new([Constraint::Jigsaw(Box::new(Constraint::square_map()))])
+            .houses()
+            .collect::<Vec<_>>(),
+        Rules::classic().houses().collect::<Vec<_>>()
+    );
+
+    let map = "000111222\
+               030111122\
+               030412222\
+               030415555\
+               333444555\
+               333444455\
+               666777888\
+               667777888\
+               666677888"
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .map(|id| id as usize)
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap();
+    let board: SudokuBoard =
+        ".39.5....578..9.212....3.......86215..6...8.3............4185.7.......6.3.5...489"
+            .parse()
+            .unwrap();
+    let rules = Rules::new([Constraint::Jigsaw(Box::new(map))]);
+    // (1, 1) belongs to the region reaching down the left side
+    assert!(rules.peers((1, 1)).contains(&(0, 4)));
+    assert!(!rules.peers((1, 1)).contains(&(0, 0)));
+
+    let solution = Solver::new()
+        .with_constraint(Constraint::Jigsaw(Box::new(map)))
+        .solve(&board)
+        .expect("Sudoku board has a solution");
+    assert!(rules.is_solution(&solution));
+    assert_eq!(
+        solution.to_string(),
+        "639251748\n578649321\n241873956\n794386215\n126594873\n853127694\n962418537\n487935162\n315762489"
+    );
+}
+
+#[test]
+fn test_killer() {
+    use crate::Solver;
+
+    let map = "AABBCCCDE\
+               FABGGGCDH\
+               FFIIIJJHH\
+               KLMMMNNNO\
+               KPPMQRROO\
+               KPPQQSSTT\
+               UUVWWSSXX\
+               UYYYZZaXb\
+               UcYdeeeXb";
+    let sums = [
+        15, 12, 27, 5, 2, 16, 15, 21, 15, 7, 19, 5, 30, 7, 13, 12, 16, 10, 21, 11, 20, 1, 8, 22,
+        24, 10, 6, 14, 4, 2, 15,
+    ];
+    let mut ids = Vec::new();
+    for id in map.chars() {
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    let cages = ids
+        .into_iter()
+        .zip(sums)
+        .map(|(id, sum)| Cage {
+            cells: SudokuBoard::iter_positions()
+                .filter(|&(x, y)| map.chars().nth(y * SIDE + x) == Some(id))
+                .collect(),
+            sum,
+        })
+        .collect::<Vec<_>>();
+
+    let rules = Rules::new(cages.iter().cloned().map(Constraint::Cage));
+    assert_eq!(rules.cages().count(), sums.len());
+    // (0, 0) shares a cage with (1, 0) and (1, 1) on top of its row and square
+    assert_eq!(rules.peers((0, 0)).len(), 20);
+    assert!(rules.peers((1, 1)).contains(&(1, 0)));
+    assert!(rules.peers((0, 2)).contains(&(0, 1)));
+
+    // the givens are only the cage sums
+    let solver = cages.iter().cloned().fold(Solver::new(), |solver, cage| {
+        solver.with_constraint(Constraint::Cage(cage))
+    });
+    let board = SudokuBoard::empty();
+    assert_eq!(solver.count_solutions(&board, 2), 1);
+    let solution = solver.solve(&board).expect("Sudoku board has a solution");
+    assert!(rules.is_solution(&solution));
+    assert_eq!(
+        solution.to_string(),
+        "534678912\n672195348\n198342567\n859761423\n426853791\n713924856\n961537284\n287419635\n345286179"
+    );
+    for cage in &cages {
+        let total = cage
+            .cells
+            .iter()
+            .map(|&pos| solution[pos].map_or(0, usize::from))
+            .sum::<usize>();
+        assert_eq!(total, cage.sum);
+    }
+
+    // two cells adding up to 5 can't hold anything above a 4
+    let mut possibilities_board = PossibilitySpaceBoard::new(&board);
+    assert!(rules.prune_cages(&mut possibilities_board));
+    assert_eq!(
+        possibilities_board[(7, 0)].iter().collect::<Vec<_>>(),
+        [Space::One, Space::Two, Space::Three, Space::Four]
+    );
+
+    // (5, 5) and (6, 6) share a cage but no row, column or square
+    let mut repeated = SudokuBoard::empty();
+    repeated[(5, 5)] = Some(Space::Seven);
+    repeated[(6, 6)] = Some(Space::Seven);
+    assert_eq!(repeated.validate(), Ok(()));
+    assert_eq!(
+        rules.validate(&repeated),
+        Err(String::from(
+            "(5, 5) and (6, 6) share a cage: duplicate Seven"
+        ))
+    );
+
+    // a cage can overflow before it is full
+    let mut overflowing = SudokuBoard::empty();
+    overflowing[(7, 0)] = Some(Space::Nine);
+    assert_eq!(
+        rules.validate(&overflowing),
+        Err(String::from("Cage at (7, 0) adds up to 9, not 5"))
+    );
+
+    // swapping the first two rows keeps the grid valid but not the cages
+    let wrong_sum = Board::from_fn(|(x, y)| match y {
+        0 => solution[(x, 1)],
+        1 => solution[(x, 0)],
+        _ => solution[(x, y)],
+    });
+    assert_eq!(wrong_sum.validate(), Ok(()));
+    assert!(!rules.is_solution(&wrong_sum));
+}
+
+#[test]
+#[should_panic(expected = "cage cell (9, 0) is off the board")]
+fn test_cage_off_board() {
+    Rules::new([Constraint::Cage(Cage {
+        cells: vec![(8, 0), (9, 0)],
+        sum: 3,
+    })]);
+}
+
+#[test]
+fn test_parity() {
+    use crate::Solver;
+
+    let board: SudokuBoard =
+        "....61..56..93.4...7..4.....9....2..813......2....4..31..65.....3.....8........5."
+            .parse()
+            .unwrap();
+    let marks = [
+        ((0, 0), Parity::Even),
+        ((4, 4), Parity::Odd),
+        ((8, 8), Parity::Odd),
+        ((2, 6), Parity::Odd),
+        ((6, 2), Parity::Even),
+        ((4, 0), Parity::Even),
+        ((4, 8), Parity::Even),
+        ((0, 4), Parity::Even),
+        ((8, 4), Parity::Even),
+    ];
+    let mut map = [None; CELLS];
+    for ((x, y), parity) in marks {
+        map[y * SIDE + x] = Some(parity);
+    }
+    let rules = Rules::new([Constraint::Parity(Box::new(map))]);
+
+    let solver = Solver::new().with_constraint(Constraint::Parity(Box::new(map)));
+    assert_eq!(solver.count_solutions(&board, 2), 1);
+    let solution = solver.solve(&board).expect("Sudoku board has a solution");
+    assert!(rules.is_solution(&solution));
+    for (pos, parity) in marks {
+        assert!(parity.allows(solution[pos].unwrap()));
+    }
+    assert_eq!(
+        solution.to_string(),
+        "429861735\n651937428\n378542619\n794385261\n813296574\n265714893\n187653942\n532479186\n946128357"
+    );
+
+    let mut odd = board.clone();
+    odd[(0, 0)] = Some(Space::Three);
+    assert_eq!(odd.validate(), Ok(()));
+    assert_eq!(
+        rules.validate(&odd),
+        Err(String::from("(0, 0) holds Three, but must be even"))
+    );
+    assert_eq!(solver.solve(&odd), None);
+
+    // without the marks the givens are ambiguous
+    assert_eq!(board.count_solutions(2), 2);
+}
@@ -0,0 +1,62 @@
+//! Serde support, enabled with the `serde` feature.
+//!
+//! A [`SudokuBoard`] serializes as a flat, row-major array of 81 cells, where
+//! an empty cell is `null` and a filled cell is its digit:
+//!
+//! ```json
+//! [5, 3, null, null, 7, null, null, null, null, 6, ...]
+//! ```
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Board, Space};
+
+impl Serialize for Space {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(usize::from(*self) as u8)
+    }
+}
+
+impl<'de> Deserialize<'de> for Space {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let digit = u8::deserialize(deserializer)?;
+        Space::try_from(digit as usize).map_err(|_| {
+            de::Error::invalid_value(
+                de::Unexpected::Unsigned(digit.into()),
+                &"a digit from 1 to 9",
+            )
+        })
+    }
+}
+
+impl<Cell: Serialize> Serialize for Board<Cell> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de, Cell: Deserialize<'de>> Deserialize<'de> for Board<Cell> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let cells = Vec::<Cell>::deserialize(deserializer)?;
+        let found = cells.len();
+        let cells = cells
+            .try_into()
+            .map_err(|_| de::Error::invalid_length(found, &"81 cells"))?;
+        Ok(Board(cells))
+    }
+}
+
+#[test]
+fn test_serde_round_trip() {
+    use crate::SudokuBoard;
+
+    let board = crate::easy_puzzle();
+    let json = serde_json::to_string(&board).unwrap();
+    assert!(json.starts_with("[5,3,null,null,7,null,"));
+    let round_tripped: SudokuBoard = serde_json::from_str(&json).unwrap();
+    assert_eq!(round_tripped, board);
+
+    assert!(serde_json::from_str::<SudokuBoard>("[1,2,3]").is_err());
+    let out_of_range = json.replacen('5', "10", 1);
+    assert!(serde_json::from_str::<SudokuBoard>(&out_of_range).is_err());
+}
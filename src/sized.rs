@@ -0,0 +1,407 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use crate::{dlx::exact_cover, BoardPosition, Space, SudokuBoard, BOX_SIZE, CELLS};
+
+/// Smallest box width [`SizedBoard`] supports, for a 4x4 board.
+pub const MIN_BOX_SIZE: usize = 2;
+/// Largest box width [`SizedBoard`] supports, as values past 9 are written
+/// with the letters A to P.
+pub const MAX_BOX_SIZE: usize = 5;
+
+/// A board of any box size from [`MIN_BOX_SIZE`] to [`MAX_BOX_SIZE`]: 4x4
+/// with 2x2 boxes, 16x16 with 4x4 boxes, and so on. Values run from 1 to the
+/// side length.
+///
+/// This is a scoped-down stand-in for making [`SudokuBoard`] generic over its
+/// box size, which stable Rust can't express for a fixed-size array of
+/// `box_size.pow(4)` cells. Only validation, solving and counting are
+/// provided, and solving always uses dancing links. Hints, techniques,
+/// [`Rules`] variants, rendering and serde all stay 9x9 only, on
+/// [`SudokuBoard`]; convert between the two with `From` and `TryFrom`.
+///
+/// [`Rules`]: crate::Rules
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct SizedBoard {
+    box_size: usize,
+    cells: Vec<Option<u8>>,
+}
+
+/// Why a string couldn't be read as a [`SizedBoard`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseSizedBoardError {
+    /// A character that isn't a value or an empty-cell marker, counting
+    /// from 0 among the characters that aren't whitespace.
+    InvalidChar { ch: char, index: usize },
+    /// A number of cells that isn't the square of a supported side length.
+    WrongCellCount { found: usize },
+    /// A value larger than the side length of the board.
+    ValueTooLarge { value: u8, index: usize },
+}
+
+impl Display for ParseSizedBoardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseSizedBoardError::InvalidChar { ch, index } => {
+                write!(f, "Character '{ch}' for cell {index} is not a value")
+            }
+            ParseSizedBoardError::WrongCellCount { found } => {
+                let mut counts = (MIN_BOX_SIZE..=MAX_BOX_SIZE)
+                    .map(|box_size| box_size.pow(4).to_string())
+                    .collect::<Vec<_>>();
+                let last = counts.pop().expect("at least one box size is supported");
+                write!(
+                    f,
+                    "{found} cells don't make a board; expected {} or {last}",
+                    counts.join(", ")
+                )
+            }
+            ParseSizedBoardError::ValueTooLarge { value, index } => {
+                write!(
+                    f,
+                    "Value {value} for cell {index} is too large for the board"
+                )
+            }
+        }
+    }
+}
+
+impl Error for ParseSizedBoardError {}
+
+impl SizedBoard {
+    /// A board with every cell empty and boxes `box_size` cells wide.
+    ///
+    /// # Panics
+    ///
+    /// If `box_size` is outside [`MIN_BOX_SIZE`] to [`MAX_BOX_SIZE`].
+    pub fn empty(box_size: usize) -> SizedBoard {
+        assert!(
+            (MIN_BOX_SIZE..=MAX_BOX_SIZE).contains(&box_size),
+            "box size {box_size} is outside {MIN_BOX_SIZE} to {MAX_BOX_SIZE}"
+        );
+        let side = box_size * box_size;
+        SizedBoard {
+            box_size,
+            cells: vec![None; side * side],
+        }
+    }
+
+    /// Width and height of a single box.
+    pub fn box_size(&self) -> usize {
+        self.box_size
+    }
+
+    /// Width and height of the whole board, and the number of distinct values.
+    pub fn side(&self) -> usize {
+        self.box_size * self.box_size
+    }
+
+    /// Every value of every cell in reading order, `None` where empty.
+    pub fn iter(&self) -> impl Iterator<Item = Option<u8>> + '_ {
+        self.cells.iter().copied()
+    }
+
+    /// Why the board breaks the rules, if it does: a value outside 1 to the
+    /// side length, or a value twice in one row, column or box.
+    pub fn validate(&self) -> Result<(), String> {
+        let (side, box_size) = (self.side(), self.box_size);
+        for (i, value) in self.iter().enumerate() {
+            if value.is_some_and(|value| value == 0 || usize::from(value) > side) {
+                let (x, y) = (i % side, i / side);
+                return Err(format!("({x}, {y}) holds {}", value.unwrap()));
+            }
+        }
+        let regions = (0..side).flat_map(|i| {
+            let (bx, by) = ((i % box_size) * box_size, (i / box_size) * box_size);
+            [
+                ("Row", (0..side).map(|j| (j, i)).collect::<Vec<_>>()),
+                ("Column", (0..side).map(|j| (i, j)).collect()),
+                (
+                    "Box",
+                    (0..side)
+                        .map(|j| (bx + j % box_size, by + j / box_size))
+                        .collect(),
+                ),
+            ]
+            .map(move |(kind, cells)| (kind, i, cells))
+        });
+        for (kind, i, cells) in regions {
+            let mut seen = vec![false; side + 1];
+            for pos in cells {
+                if let Some(value) = self[pos] {
+                    if std::mem::replace(&mut seen[usize::from(value)], true) {
+                        return Err(format!("{kind} {i} is invalid: duplicate {value}"));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether every cell is filled and the board breaks no rule.
+    pub fn is_solution(&self) -> bool {
+        self.cells.iter().all(Option::is_some) && self.validate().is_ok()
+    }
+
+    /// Up to `limit` completions of this board, none if it breaks the rules.
+    fn solutions(&self, limit: usize) -> Vec<SizedBoard> {
+        if self.validate().is_err() {
+            return Vec::new();
+        }
+        let givens = self
+            .iter()
+            .map(|value| value.map(|value| usize::from(value) - 1));
+        exact_cover(self.box_size, givens, limit)
+            .into_iter()
+            .map(|values| SizedBoard {
+                box_size: self.box_size,
+                cells: values.into_iter().map(|v| Some(v as u8 + 1)).collect(),
+            })
+            .collect()
+    }
+
+    /// Searches for a completed board, returning `None` if there is no solution.
+    pub fn solve(&self) -> Option<SizedBoard> {
+        self.solutions(1).pop()
+    }
+
+    /// Counts the distinct solutions of this board, stopping once `limit` are found.
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        self.solutions(limit).len()
+    }
+
+    /// Whether exactly one completion of this board exists.
+    pub fn has_unique_solution(&self) -> bool {
+        self.count_solutions(2) == 1
+    }
+}
+
+impl Index<BoardPosition> for SizedBoard {
+    type Output = Option<u8>;
+
+    fn index(&self, (x, y): BoardPosition) -> &Self::Output {
+        assert!(
+            x < self.side() && y < self.side(),
+            "({x}, {y}) is off the board"
+        );
+        &self.cells[y * self.side() + x]
+    }
+}
+
+impl IndexMut<BoardPosition> for SizedBoard {
+    fn index_mut(&mut self, (x, y): BoardPosition) -> &mut Self::Output {
+        assert!(
+            x < self.side() && y < self.side(),
+            "({x}, {y}) is off the board"
+        );
+        let side = self.side();
+        &mut self.cells[y * side + x]
+    }
+}
+
+/// The character for `value`: 1 to 9, then A onwards.
+fn value_char(value: u8) -> char {
+    char::from_digit(u32::from(value), 36)
+        .map(|ch| ch.to_ascii_uppercase())
+        .unwrap_or('?')
+}
+
+impl Display for SizedBoard {
+    /// One row per line, with `.` for empty cells and letters from A for
+    /// values past 9.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rows = self
+            .cells
+            .chunks(self.side())
+            .map(|row| {
+                row.iter()
+                    .map(|value| value.map_or('.', value_char))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        write!(f, "{}", rows.join("\n"))
+    }
+}
+
+impl FromStr for SizedBoard {
+    type Err = ParseSizedBoardError;
+
+    /// Reads one character per cell in reading order, on any number of lines:
+    /// `1` to `9` then `A` onwards (in either case) for values, and `.` or
+    /// `0` for empty cells. Whitespace is skipped, and the number of cells
+    /// sets the size of the board.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cells = s
+            .chars()
+            .filter(|ch| !ch.is_whitespace())
+            .enumerate()
+            .map(|(index, ch)| match ch {
+                '.' | '0' => Ok(None),
+                _ => ch
+                    .to_digit(36)
+                    .map(|value| Some(value as u8))
+                    .ok_or(ParseSizedBoardError::InvalidChar { ch, index }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let found = cells.len();
+        let box_size = (MIN_BOX_SIZE..=MAX_BOX_SIZE)
+            .find(|box_size| box_size.pow(4) == found)
+            .ok_or(ParseSizedBoardError::WrongCellCount { found })?;
+        let side = box_size * box_size;
+        let too_large = cells.iter().enumerate().find_map(|(index, value)| {
+            let value = value.filter(|&value| usize::from(value) > side)?;
+            Some(ParseSizedBoardError::ValueTooLarge { value, index })
+        });
+        if let Some(err) = too_large {
+            return Err(err);
+        }
+        Ok(SizedBoard { box_size, cells })
+    }
+}
+
+impl From<&SudokuBoard> for SizedBoard {
+    fn from(board: &SudokuBoard) -> Self {
+        SizedBoard {
+            box_size: BOX_SIZE,
+            cells: board
+                .iter()
+                .map(|space| space.map(|space| usize::from(space) as u8))
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<&SizedBoard> for SudokuBoard {
+    type Error = String;
+
+    /// Fails unless `board` is 9x9.
+    fn try_from(board: &SizedBoard) -> Result<Self, Self::Error> {
+        if board.cells.len() != CELLS {
+            return Err(format!("A {0}x{0} board isn't a 9x9 sudoku", board.side()));
+        }
+        if let Some(value) = board
+            .iter()
+            .flatten()
+            .find(|&value| value == 0 || value > 9)
+        {
+            return Err(format!("{value} isn't a value from 1 to 9"));
+        }
+        let mut sudoku = SudokuBoard::empty();
+        for (pos, value) in SudokuBoard::iter_positions().zip(board.iter()) {
+            sudoku[pos] = value.map(|value| Space::all()[usize::from(value) - 1]);
+        }
+        Ok(sudoku)
+    }
+}
+
+/// A full board with boxes `box_size` wide, shifting each row along from
+/// the one above.
+#[cfg(test)]
+fn pattern(box_size: usize) -> SizedBoard {
+    let mut board = SizedBoard::empty(box_size);
+    let side = board.side();
+    for y in 0..side {
+        for x in 0..side {
+            let shift = (y % box_size) * box_size + y / box_size;
+            board[(x, y)] = Some(((x + shift) % side + 1) as u8);
+        }
+    }
+    board
+}
+
+#[test]
+fn test_sized_4x4() {
+    let board: SizedBoard = "1...
+                             ..3.
+                             .4..
+                             ...2"
+        .parse()
+        .unwrap();
+    assert_eq!((board.box_size(), board.side()), (2, 4));
+    let solution = board.solve().expect("board has a solution");
+    assert!(solution.is_solution());
+    assert_eq!(solution.to_string(), "1324\n4231\n2413\n3142");
+    assert_eq!(board.count_solutions(5), 1);
+    assert!(board.has_unique_solution());
+
+    let mut clash = board.clone();
+    clash[(1, 0)] = Some(1);
+    assert_eq!(
+        clash.validate(),
+        Err(String::from("Row 0 is invalid: duplicate 1"))
+    );
+    assert_eq!(clash.solve(), None);
+}
+
+#[test]
+fn test_sized_16x16() {
+    let full = pattern(4);
+    assert!(full.is_solution());
+    let mut board = full.clone();
+    for i in (0..256).filter(|i| i % 3 != 0) {
+        board[(i % 16, i / 16)] = None;
+    }
+    let solution = board.solve().expect("board has a solution");
+    assert!(solution.is_solution());
+    assert!(board
+        .iter()
+        .zip(solution.iter())
+        .all(|(given, value)| given.is_none() || given == value));
+
+    let text = full.to_string();
+    assert_eq!(text.lines().next(), Some("123456789ABCDEFG"));
+    assert_eq!(text.parse::<SizedBoard>(), Ok(full));
+    assert_eq!(
+        SizedBoard::empty(4)
+            .solve()
+            .map(|solution| solution.is_solution()),
+        Some(true)
+    );
+}
+
+#[test]
+fn test_sized_9x9() {
+    let sudoku = crate::easy_puzzle();
+    let board = SizedBoard::from(&sudoku);
+    assert_eq!(board.box_size(), BOX_SIZE);
+    let solution = board.solve().expect("board has a solution");
+    assert_eq!(SudokuBoard::try_from(&solution).ok(), sudoku.solve());
+    assert_eq!(SudokuBoard::try_from(&board), Ok(sudoku));
+    assert!(SudokuBoard::try_from(&pattern(2)).is_err());
+}
+
+#[test]
+fn test_parse_sized() {
+    let wrong_count = "12345".parse::<SizedBoard>();
+    assert_eq!(
+        wrong_count,
+        Err(ParseSizedBoardError::WrongCellCount { found: 5 })
+    );
+    assert_eq!(
+        wrong_count.unwrap_err().to_string(),
+        "5 cells don't make a board; expected 16, 81, 256 or 625"
+    );
+    // every board that can be made prints in a form that parses back
+    for box_size in MIN_BOX_SIZE..=MAX_BOX_SIZE {
+        let board = SizedBoard::empty(box_size);
+        assert_eq!(board.to_string().parse(), Ok(board));
+    }
+    assert_eq!(
+        "1..5............".parse::<SizedBoard>(),
+        Err(ParseSizedBoardError::ValueTooLarge { value: 5, index: 3 })
+    );
+    assert_eq!(
+        "1..?............".parse::<SizedBoard>(),
+        Err(ParseSizedBoardError::InvalidChar { ch: '?', index: 3 })
+    );
+    assert_eq!(
+        "g".repeat(256)
+            .parse::<SizedBoard>()
+            .map(|board| board[(0, 0)]),
+        Ok(Some(16))
+    );
+}
@@ -0,0 +1,570 @@
+use std::{
+    cell::RefCell,
+    error::Error,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    rc::Rc,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use log::debug;
+use rand::{rngs::StdRng, seq::SliceRandom, RngCore, SeedableRng};
+use space_search::{search::*, Scoreable, Searchable, Searcher, SolutionIdentifiable};
+
+use crate::{
+    techniques::{self, TECHNIQUES},
+    Constraint, NextBoardStates, Rules, SudokuBoard, Technique,
+};
+
+type ProgressCallback<'a> = Box<dyn FnMut(usize) + 'a>;
+type StateCallback<'a> = Box<dyn FnMut(&SudokuBoard) + 'a>;
+
+/// Nodes expanded between looks at the clock when a timeout is set.
+const TIMEOUT_CHECK_INTERVAL: usize = 16;
+
+/// Counters gathered over one search, for comparing heuristics.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SearchStats {
+    pub nodes_expanded: usize,
+    /// Expanded nodes that turned out to have no solution.
+    pub backtracks: usize,
+    /// Most expansions between the starting board and any node.
+    pub max_depth: usize,
+    /// Cells filled by reduction rather than by branching.
+    pub singles_placed: usize,
+    pub elapsed: Duration,
+}
+
+/// The order a [`Solver`] tries a cell's candidates in when it has to guess.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ValueOrder {
+    /// From 1 up, so the order of solutions is stable.
+    #[default]
+    Ascending,
+    /// The values that rule out the fewest candidates among the cell's peers
+    /// first, breaking ties from 1 up. Most branch points have only two
+    /// candidates, so this rarely saves nodes over [`ValueOrder::Ascending`].
+    LeastConstraining,
+}
+
+/// Why [`SudokuBoard::solve_within_nodes`] gave up without an answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NodeLimitExceeded {
+    pub max_nodes: usize,
+}
+
+impl Display for NodeLimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Gave up after expanding {} search nodes without an answer",
+            self.max_nodes
+        )
+    }
+}
+
+impl Error for NodeLimitExceeded {}
+
+struct SearchContext<'a> {
+    rules: Rules,
+    /// Tried in order once singles stall, before guessing.
+    techniques: Vec<Technique>,
+    /// Whether to branch on boards reduction can't finish, or give up on them.
+    guessing: bool,
+    value_order: ValueOrder,
+    stats: SearchStats,
+    progress: Option<(usize, ProgressCallback<'a>)>,
+    /// Shown every board the search moves to, reduced or guessed.
+    on_state: Option<StateCallback<'a>>,
+    shuffle: Option<Box<dyn RngCore + 'a>>,
+    timeout: Option<Duration>,
+    deadline: Option<Instant>,
+    node_limit: Option<usize>,
+    /// The expansion count past which the current search gives up.
+    node_cap: Option<usize>,
+    /// Raised from elsewhere to abandon the search.
+    cancel: Option<&'a AtomicBool>,
+    /// Set once the deadline passes, the node limit is reached, or the search
+    /// is cancelled, after which
+    /// every node is a dead end.
+    stopped: bool,
+}
+
+/// Search state that reports back to its [`Solver`] as it is expanded.
+#[derive(Clone)]
+struct SearchNode<'a> {
+    board: SudokuBoard,
+    depth: usize,
+    context: Rc<RefCell<SearchContext<'a>>>,
+}
+
+impl Hash for SearchNode<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.board.hash(state);
+    }
+}
+
+impl PartialEq for SearchNode<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.board == other.board
+    }
+}
+
+impl Eq for SearchNode<'_> {}
+
+impl Searchable for SearchNode<'_> {
+    fn next_states(&self) -> impl Iterator<Item = Self> {
+        let mut context = self.context.borrow_mut();
+        if context.stopped {
+            return Vec::new().into_iter();
+        }
+        let stats = &mut context.stats;
+        stats.nodes_expanded += 1;
+        stats.max_depth = stats.max_depth.max(self.depth);
+        let nodes = stats.nodes_expanded;
+        debug!("expanding search node {nodes}");
+        if let Some((interval, mut callback)) = context.progress.take() {
+            // the callback runs with the context released, so it can use the
+            // solver, and is put back unless it set a new one
+            if nodes.is_multiple_of(interval) {
+                drop(context);
+                callback(nodes);
+                context = self.context.borrow_mut();
+            }
+            context.progress.get_or_insert((interval, callback));
+        }
+        if context.node_cap.is_some_and(|cap| nodes > cap) {
+            debug!("search hit its node limit after {} nodes", nodes - 1);
+            context.stopped = true;
+            return Vec::new().into_iter();
+        }
+        if context
+            .cancel
+            .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+        {
+            debug!("search cancelled after {nodes} nodes");
+            context.stopped = true;
+            return Vec::new().into_iter();
+        }
+        if let Some(deadline) = context.deadline {
+            if (nodes - 1).is_multiple_of(TIMEOUT_CHECK_INTERVAL) && Instant::now() >= deadline {
+                debug!("search timed out after {nodes} nodes");
+                context.stopped = true;
+                return Vec::new().into_iter();
+            }
+        }
+
+        let next_boards =
+            self.board
+                .next_states_with(&context.rules, &context.techniques, context.value_order);
+        let guessing = context.guessing;
+        let stats = &mut context.stats;
+        match &next_boards {
+            NextBoardStates::Single(None) => stats.backtracks += 1,
+            NextBoardStates::Single(Some(reduced)) => {
+                stats.singles_placed += self.board.empty_count() - reduced.empty_count();
+            }
+            NextBoardStates::States(_) if !guessing => {
+                stats.backtracks += 1;
+                return Vec::new().into_iter();
+            }
+            NextBoardStates::States(_) => {}
+        }
+        let next_boards = next_boards.collect::<Vec<_>>();
+        if let Some(mut on_state) = context.on_state.take() {
+            drop(context);
+            next_boards.iter().for_each(&mut on_state);
+            context = self.context.borrow_mut();
+            context.on_state.get_or_insert(on_state);
+        }
+        let mut next_states = next_boards
+            .into_iter()
+            .map(|board| SearchNode {
+                board,
+                depth: self.depth + 1,
+                context: self.context.clone(),
+            })
+            .collect::<Vec<_>>();
+        if let Some(rng) = &mut context.shuffle {
+            next_states.shuffle(rng);
+        }
+        next_states.into_iter()
+    }
+}
+
+impl SolutionIdentifiable for SearchNode<'_> {
+    fn is_solution(&self) -> bool {
+        let context = self.context.borrow();
+        !context.stopped && context.rules.is_solution(&self.board)
+    }
+}
+
+impl Scoreable for SearchNode<'_> {
+    type Score = <SudokuBoard as Scoreable>::Score;
+
+    fn score(&self) -> Self::Score {
+        self.board.score()
+    }
+}
+
+/// Runs the board search, with optional hooks into its progress.
+pub struct Solver<'a> {
+    context: Rc<RefCell<SearchContext<'a>>>,
+}
+
+impl Default for Solver<'_> {
+    fn default() -> Self {
+        Solver::new()
+    }
+}
+
+impl<'a> Solver<'a> {
+    pub fn new() -> Self {
+        Solver {
+            context: Rc::new(RefCell::new(SearchContext {
+                rules: Rules::classic().clone(),
+                techniques: techniques::sound(),
+                guessing: true,
+                value_order: ValueOrder::default(),
+                stats: SearchStats::default(),
+                progress: None,
+                on_state: None,
+                shuffle: None,
+                timeout: None,
+                deadline: None,
+                node_limit: None,
+                node_cap: None,
+                cancel: None,
+                stopped: false,
+            })),
+        }
+    }
+
+    /// Calls `callback` with the number of expanded nodes every `interval` nodes.
+    /// The callback may use this solver, but isn't called for the searches it
+    /// starts.
+    pub fn on_progress(self, interval: usize, callback: impl FnMut(usize) + 'a) -> Self {
+        assert!(interval > 0, "progress interval must be nonzero");
+        self.context.borrow_mut().progress = Some((interval, Box::new(callback)));
+        self
+    }
+
+    /// Calls `callback` with every board the search reaches: the board left
+    /// once reduction stalls, and each board made by guessing a cell's value.
+    /// Like [`Solver::on_progress`], the callback may use this solver.
+    pub fn on_state(self, callback: impl FnMut(&SudokuBoard) + 'a) -> Self {
+        self.context.borrow_mut().on_state = Some(Box::new(callback));
+        self
+    }
+
+    /// Adds a variant rule that solutions must also satisfy.
+    ///
+    /// # Panics
+    ///
+    /// If the rules it makes are invalid, as described on [`Rules::new`].
+    pub fn with_constraint(self, constraint: Constraint) -> Self {
+        {
+            let rules = &mut self.context.borrow_mut().rules;
+            let constraints = rules.constraints().iter().cloned().chain([constraint]);
+            *rules = Rules::new(constraints);
+        }
+        self
+    }
+
+    /// Turns `technique` on or off for reducing boards before guessing. Naked
+    /// and hidden singles are always used, and every technique that doesn't
+    /// assume a unique solution starts out on. Turning on one that does can
+    /// lose solutions of boards with several.
+    pub fn with_technique(self, technique: Technique, enabled: bool) -> Self {
+        {
+            let techniques = &mut self.context.borrow_mut().techniques;
+            *techniques = TECHNIQUES
+                .iter()
+                .copied()
+                .filter(|&other| match other == technique {
+                    true => enabled,
+                    false => techniques.contains(&other),
+                })
+                .collect();
+        }
+        self
+    }
+
+    /// Whether to guess at a cell once reduction stalls, as by default. With
+    /// guessing off, only boards the enabled techniques finish are solved,
+    /// and the rest have no solutions as far as the search goes.
+    pub fn allow_guessing(self, allowed: bool) -> Self {
+        self.context.borrow_mut().guessing = allowed;
+        self
+    }
+
+    /// Sets the order values are tried in when guessing at a cell, ascending
+    /// by default. [`Solver::shuffled`] overrides it.
+    pub fn value_order(self, order: ValueOrder) -> Self {
+        self.context.borrow_mut().value_order = order;
+        self
+    }
+
+    /// Gives up once `timeout` has passed since a search began, as if no
+    /// solutions remained.
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        self.context.borrow_mut().timeout = Some(timeout);
+        self
+    }
+
+    /// Gives up as if no solutions remained once a search has expanded
+    /// `max_nodes` nodes.
+    pub fn with_node_limit(self, max_nodes: usize) -> Self {
+        self.context.borrow_mut().node_limit = Some(max_nodes);
+        self
+    }
+
+    /// Gives up as if no solutions remained once `cancel` is set.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn cancelled_by(self, cancel: &'a AtomicBool) -> Self {
+        self.context.borrow_mut().cancel = Some(cancel);
+        self
+    }
+
+    /// Explores branches in an order drawn from `rng` rather than from 1 to 9.
+    /// The same generator state always gives the same order, so a seeded
+    /// `rng` makes randomized solving and generation reproducible.
+    pub fn shuffled(self, rng: impl RngCore + 'a) -> Self {
+        self.context.borrow_mut().shuffle = Some(Box::new(rng));
+        self
+    }
+
+    /// Like [`Solver::shuffled`] with a generator seeded from `seed`.
+    pub fn with_seed(self, seed: u64) -> Self {
+        self.shuffled(StdRng::seed_from_u64(seed))
+    }
+
+    /// Lazily yields every completion of `board`.
+    pub fn solutions(&self, board: &SudokuBoard) -> impl Iterator<Item = SudokuBoard> + 'a {
+        {
+            let mut context = self.context.borrow_mut();
+            context.deadline = context.timeout.map(|timeout| Instant::now() + timeout);
+            context.node_cap = context
+                .node_limit
+                .map(|limit| context.stats.nodes_expanded + limit);
+            context.stopped = false;
+        }
+        let root = SearchNode {
+            board: board.clone(),
+            depth: 0,
+            context: self.context.clone(),
+        };
+        let searcher: Searcher<guided::no_route::hashable::Manager<_>, _> = Searcher::new(root);
+        searcher.map(|node| node.board)
+    }
+
+    /// Searches for a completed board, returning `None` if there is no solution.
+    pub fn solve(&self, board: &SudokuBoard) -> Option<SudokuBoard> {
+        self.solutions(board).next()
+    }
+
+    /// Like [`Solver::solve`], also reporting how the search went.
+    pub fn solve_with_stats(&self, board: &SudokuBoard) -> (Option<SudokuBoard>, SearchStats) {
+        self.context.borrow_mut().stats = SearchStats::default();
+        let start = Instant::now();
+        let solution = self.solve(board);
+        let mut stats = self.context.borrow().stats;
+        stats.elapsed = start.elapsed();
+        (solution, stats)
+    }
+
+    /// Counts the distinct solutions of `board`, stopping once `limit` are found.
+    pub fn count_solutions(&self, board: &SudokuBoard, limit: usize) -> usize {
+        self.solutions(board).take(limit).count()
+    }
+}
+
+#[test]
+fn test_progress_callback() {
+    use std::cell::Cell;
+
+    let board_str = "\
+..65.....
+7.5..23..
+.3.....8.
+.5..96.7.
+1.4.....8
+...82....
+.2.....9.
+..72..4..
+.....75..";
+    let board: SudokuBoard = board_str.parse().unwrap();
+    let calls = Cell::new(0);
+    let solution = Solver::new()
+        .on_progress(1, |_| calls.set(calls.get() + 1))
+        .solve(&board)
+        .expect("Sudoku board has a solution");
+    assert_eq!(solution.validate(), Ok(()));
+    assert!(calls.get() > 0);
+}
+
+#[test]
+fn test_solve_observed() {
+    let board: SudokuBoard =
+        "..65.....7.5..23...3.....8..5..96.7.1.4.....8...82.....2.....9...72..4.......75.."
+            .parse()
+            .unwrap();
+    let mut states = Vec::new();
+    let solution = board.solve_observed(|state| states.push(state.clone()));
+    assert_eq!(solution, board.solve());
+    assert!(states.len() > 1);
+    // the first move is reduction, which fills in cells without guessing
+    assert!(states[0].empty_count() < board.empty_count());
+    assert!(states.iter().all(|state| board
+        .iter_filled()
+        .all(|(pos, space)| state[pos] == Some(space))));
+    assert!(states.contains(&solution.unwrap()));
+}
+
+#[test]
+fn test_reentrant_callbacks() {
+    use std::cell::Cell;
+
+    let board: SudokuBoard =
+        "..65.....7.5..23...3.....8..5..96.7.1.4.....8...82.....2.....9...72..4.......75.."
+            .parse()
+            .unwrap();
+    let (states, progress) = (Cell::new(0), Cell::new(0));
+    let (states_ref, progress_ref, board_ref) = (&states, &progress, &board);
+    // both callbacks run searches on the solver that calls them
+    let solver = Rc::new_cyclic(|solver: &std::rc::Weak<Solver>| {
+        let (on_state, on_progress) = (solver.clone(), solver.clone());
+        Solver::new()
+            .on_state(move |state| {
+                let solver = on_state.upgrade().unwrap();
+                assert!(solver.count_solutions(state, 2) <= 1);
+                states_ref.set(states_ref.get() + 1);
+            })
+            .on_progress(1, move |_| {
+                let solver = on_progress.upgrade().unwrap();
+                assert!(solver.solve(board_ref).is_some());
+                progress_ref.set(progress_ref.get() + 1);
+            })
+    });
+    assert_eq!(solver.solve(&board), board.solve());
+    assert!(states.get() > 0);
+    assert!(progress.get() > 0);
+}
+
+#[test]
+fn test_solver_techniques() {
+    let board: SudokuBoard =
+        "......9..3......62..1..3......5..249.7.3.9.......68.7.......7.12....1...6.9...4.5"
+            .parse()
+            .unwrap();
+    let solution = board.solve();
+    assert!(solution.is_some());
+    // x-cycles finish this board, and nothing else does
+    assert_eq!(Solver::new().allow_guessing(false).solve(&board), solution);
+    let without_x_cycles = || Solver::new().with_technique(Technique::XCycles, false);
+    assert_eq!(without_x_cycles().allow_guessing(false).solve(&board), None);
+    assert_eq!(without_x_cycles().solve(&board), solution);
+    assert_eq!(
+        without_x_cycles()
+            .with_technique(Technique::XCycles, true)
+            .allow_guessing(false)
+            .solve(&board),
+        solution
+    );
+
+    let logic_only = TECHNIQUES
+        .iter()
+        .fold(Solver::new(), |solver, &technique| {
+            solver.with_technique(technique, false)
+        })
+        .allow_guessing(false);
+    assert_eq!(logic_only.solve(&board), None);
+    let easy = crate::easy_puzzle();
+    assert_eq!(logic_only.solve(&easy), Some(crate::easy_solution()));
+}
+
+#[test]
+fn test_solve_with_stats() {
+    let board: SudokuBoard =
+        "..65.....7.5..23...3.....8..5..96.7.1.4.....8...82.....2.....9...72..4.......75.."
+            .parse()
+            .unwrap();
+    let (solution, stats) = board.solve_with_stats();
+    assert_eq!(solution, board.solve());
+    // branching on the cell with the fewest candidates; the first empty cell
+    // took 12 nodes here
+    assert_eq!(stats.nodes_expanded, 4);
+    assert!(stats.backtracks < stats.nodes_expanded);
+
+    let (constrained, stats) = Solver::new()
+        .value_order(ValueOrder::LeastConstraining)
+        .solve_with_stats(&board);
+    assert_eq!(constrained, solution);
+    assert_eq!(stats.nodes_expanded, 5);
+    assert!(stats.max_depth > 0);
+    assert!(stats.singles_placed > 0);
+}
+
+#[test]
+fn test_timeout() {
+    let board: SudokuBoard =
+        "..65.....7.5..23...3.....8..5..96.7.1.4.....8...82.....2.....9...72..4.......75.."
+            .parse()
+            .unwrap();
+    assert_eq!(board.solve_with_timeout(Duration::ZERO), None);
+    assert_eq!(
+        board.solve_with_timeout(Duration::from_secs(60)),
+        board.solve()
+    );
+
+    // the deadline starts over with each search
+    let solver = Solver::new().with_timeout(Duration::from_millis(100));
+    let start = Instant::now();
+    let found = solver.count_solutions(&SudokuBoard::empty(), usize::MAX);
+    assert!(found > 0);
+    assert!(start.elapsed() < Duration::from_secs(10));
+    assert_eq!(solver.solve(&board), board.solve());
+}
+
+#[test]
+fn test_node_limit() {
+    let board: SudokuBoard =
+        "..65.....7.5..23...3.....8..5..96.7.1.4.....8...82.....2.....9...72..4.......75.."
+            .parse()
+            .unwrap();
+    assert_eq!(
+        board.solve_within_nodes(1),
+        Err(NodeLimitExceeded { max_nodes: 1 })
+    );
+    assert_eq!(board.solve_within_nodes(10_000), Ok(board.solve()));
+
+    let mut unsolvable = board.clone();
+    unsolvable[(0, 0)] = Some(crate::Space::Six);
+    assert_eq!(unsolvable.solve_within_nodes(10_000), Ok(None));
+
+    // the limit applies to each search separately, and this board takes a
+    // handful of nodes
+    let solver = Solver::new().with_node_limit(8);
+    assert_eq!(solver.solve(&board), board.solve());
+    assert_eq!(solver.solve(&board), board.solve());
+}
+
+#[test]
+fn test_seeded_order() {
+    let run = |solver: Solver| {
+        solver
+            .solutions(&SudokuBoard::empty())
+            .take(5)
+            .collect::<Vec<_>>()
+    };
+    let seeded = run(Solver::new().with_seed(7));
+    assert_eq!(run(Solver::new().with_seed(7)), seeded);
+    assert_ne!(run(Solver::new().with_seed(8)), seeded);
+    assert!(seeded.iter().all(|board| board.is_complete()));
+
+    // without a seed the order is fixed too
+    let unseeded = run(Solver::new());
+    assert_eq!(run(Solver::new()), unseeded);
+    assert_ne!(unseeded, seeded);
+}
@@ -0,0 +1,138 @@
+use crate::{
+    hint::find_single,
+    techniques::{self, TECHNIQUES},
+    BoardPosition, HintReason, Rules, Space, SudokuBoard, SudokuChoices, Technique,
+};
+
+/// One move made while solving, in the order it was made.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SolveStep {
+    pub reason: StepReason,
+    pub pos: BoardPosition,
+    pub action: StepAction,
+}
+
+/// What justified a [`SolveStep`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepReason {
+    /// A naked or hidden single.
+    Single(HintReason),
+    /// One of the more involved elimination techniques.
+    Technique(Technique),
+    /// Logic ran out, so the value was taken from a search.
+    Guess,
+}
+
+/// The change a [`SolveStep`] made to its cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepAction {
+    Place(Space),
+    Eliminate(SudokuChoices),
+}
+
+impl SudokuBoard {
+    /// Solves the board one logical move at a time, recording each move.
+    ///
+    /// Once logic stalls, the remaining cells are filled from a search and
+    /// recorded as guesses. If the board has no solution, the board is
+    /// returned as far as logic got. Techniques that assume a unique solution
+    /// are only used on boards that have one, so every elimination holds for
+    /// the solution returned.
+    pub fn solve_with_steps(&self) -> (SudokuBoard, Vec<SolveStep>) {
+        let rules = Rules::classic();
+        let techniques = if self.has_unique_solution() {
+            TECHNIQUES.to_vec()
+        } else {
+            techniques::sound()
+        };
+        let mut board = self.clone();
+        let mut possibilities_board = board.candidates();
+        let mut steps = Vec::new();
+
+        loop {
+            if let Some(hint) = find_single(rules, &board, &possibilities_board) {
+                board[hint.pos] = Some(hint.space);
+                possibilities_board[hint.pos] = SudokuChoices::one(hint.space);
+                for &pos in rules.peers(hint.pos) {
+                    possibilities_board[pos].set(hint.space, false);
+                }
+                steps.push(SolveStep {
+                    reason: StepReason::Single(hint.reason),
+                    pos: hint.pos,
+                    action: StepAction::Place(hint.space),
+                });
+                continue;
+            }
+
+            let before = possibilities_board.clone();
+            let applied = techniques
+                .iter()
+                .find(|technique| technique.apply(rules, &board, &mut possibilities_board));
+            let Some(&technique) = applied else {
+                break;
+            };
+            for pos in SudokuBoard::iter_positions() {
+                let eliminated = before[pos] & !possibilities_board[pos];
+                if !eliminated.is_empty() {
+                    steps.push(SolveStep {
+                        reason: StepReason::Technique(technique),
+                        pos,
+                        action: StepAction::Eliminate(eliminated),
+                    });
+                }
+            }
+        }
+
+        if !board.is_complete() {
+            if let Some(solution) = board.solve() {
+                for pos in board.iter_empty() {
+                    let space = solution[pos].expect("solutions have every cell filled");
+                    steps.push(SolveStep {
+                        reason: StepReason::Guess,
+                        pos,
+                        action: StepAction::Place(space),
+                    });
+                }
+                board = solution;
+            }
+        }
+
+        (board, steps)
+    }
+}
+
+#[test]
+fn test_solve_with_steps() {
+    let board = crate::easy_puzzle();
+    let (solution, steps) = board.solve_with_steps();
+    assert_eq!(solution, crate::easy_solution());
+
+    // replaying the placements reproduces the solution
+    let mut replayed = board.clone();
+    for step in &steps {
+        assert_ne!(step.reason, StepReason::Guess);
+        if let StepAction::Place(space) = step.action {
+            assert_eq!(replayed[step.pos], None);
+            replayed[step.pos] = Some(space);
+        }
+    }
+    assert_eq!(replayed, solution);
+    assert_eq!(steps.len(), board.empty_count());
+}
+
+#[test]
+fn test_solve_with_steps_techniques() {
+    use space_search::SolutionIdentifiable;
+
+    let board: SudokuBoard =
+        "......5..8.75...1........9......7.6.5...92..4..81....7.......76.3...4...7.2.35..."
+            .parse()
+            .unwrap();
+    let (solution, steps) = board.solve_with_steps();
+    assert_eq!(solution.validate(), Ok(()));
+    assert!(solution.is_solution());
+    assert!(steps
+        .iter()
+        .any(|step| step.reason == StepReason::Technique(Technique::XWing)));
+    assert!(steps.iter().all(|step| step.reason != StepReason::Guess));
+}
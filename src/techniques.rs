@@ -0,0 +1,1121 @@
+#[cfg(test)]
+use space_search::SolutionIdentifiable;
+
+use crate::{
+    BoardPosition, PossibilitySpaceBoard, Rules, Space, SudokuBoard, SudokuChoices, SudokuRegion,
+    SudokuRegion::*, BOX_SIZE, SIDE,
+};
+
+type Elimination = fn(&Rules, &SudokuBoard, &mut PossibilitySpaceBoard) -> bool;
+
+/// A deduction tried once naked and hidden singles stall.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Technique {
+    NakedPairs,
+    HiddenPairs,
+    Pointing,
+    NakedTriples,
+    HiddenTriples,
+    XWing,
+    XYWing,
+    RemotePairs,
+    Swordfish,
+    SimpleColoring,
+    XCycles,
+    /// Assumes a unique solution, so it is off in [`SudokuBoard::reduce`] and
+    /// in a new [`Solver`](crate::Solver).
+    UniqueRectangle,
+    /// Assumes a unique solution, like [`Technique::UniqueRectangle`].
+    BugPlusOne,
+}
+
+/// How hard a puzzle is to solve by hand, rated by the most advanced
+/// technique needed to finish it.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Difficulty {
+    /// Solvable with naked and hidden singles alone.
+    Easy,
+    /// Needs naked or hidden pairs.
+    Medium,
+    /// Needs pointing pairs, box-line reduction, or naked or hidden triples.
+    Hard,
+    /// Needs X-Wing, XY-Wing, remote pairs, Swordfish, simple coloring,
+    /// X-cycles, or a uniqueness argument: unique rectangles or BUG+1.
+    Expert,
+    /// Can't be finished without guessing.
+    Extreme,
+}
+
+/// Every technique, tried in this order, cheapest first.
+pub(crate) const TECHNIQUES: &[Technique] = &[
+    Technique::NakedPairs,
+    Technique::HiddenPairs,
+    Technique::Pointing,
+    Technique::NakedTriples,
+    Technique::HiddenTriples,
+    Technique::XWing,
+    Technique::XYWing,
+    Technique::RemotePairs,
+    Technique::Swordfish,
+    Technique::SimpleColoring,
+    Technique::XCycles,
+    Technique::UniqueRectangle,
+    Technique::BugPlusOne,
+];
+
+impl Technique {
+    /// The lowest difficulty that allows this technique.
+    pub fn difficulty(self) -> Difficulty {
+        match self {
+            Technique::NakedPairs | Technique::HiddenPairs => Difficulty::Medium,
+            Technique::Pointing | Technique::NakedTriples | Technique::HiddenTriples => {
+                Difficulty::Hard
+            }
+            Technique::XWing
+            | Technique::XYWing
+            | Technique::RemotePairs
+            | Technique::Swordfish
+            | Technique::SimpleColoring
+            | Technique::XCycles
+            | Technique::UniqueRectangle
+            | Technique::BugPlusOne => Difficulty::Expert,
+        }
+    }
+
+    /// Whether the technique relies on the board having a single solution,
+    /// and so can rule out real solutions of a board with several.
+    pub fn assumes_unique(self) -> bool {
+        matches!(self, Technique::UniqueRectangle | Technique::BugPlusOne)
+    }
+
+    /// Eliminates candidates, returning whether anything changed.
+    pub(crate) fn apply(
+        self,
+        rules: &Rules,
+        board: &SudokuBoard,
+        possibilities_board: &mut PossibilitySpaceBoard,
+    ) -> bool {
+        let elimination: Elimination = match self {
+            Technique::NakedPairs => naked_pairs,
+            Technique::HiddenPairs => hidden_pairs,
+            Technique::Pointing => pointing,
+            Technique::NakedTriples => naked_triples,
+            Technique::HiddenTriples => hidden_triples,
+            Technique::XWing => x_wing,
+            Technique::XYWing => xy_wing,
+            Technique::RemotePairs => remote_pairs,
+            Technique::Swordfish => swordfish,
+            Technique::SimpleColoring => simple_coloring,
+            Technique::XCycles => x_cycles,
+            Technique::UniqueRectangle => unique_rectangle,
+            Technique::BugPlusOne => bug_plus_one,
+        };
+        elimination(rules, board, possibilities_board)
+    }
+}
+
+/// Every technique allowed at `difficulty`.
+pub(crate) fn up_to(difficulty: Difficulty) -> Vec<Technique> {
+    TECHNIQUES
+        .iter()
+        .copied()
+        .filter(|technique| technique.difficulty() <= difficulty)
+        .collect()
+}
+
+/// Every technique that holds however many solutions the board has, safe to
+/// use while searching for or counting them.
+pub(crate) fn sound() -> Vec<Technique> {
+    TECHNIQUES
+        .iter()
+        .copied()
+        .filter(|technique| !technique.assumes_unique())
+        .collect()
+}
+
+impl SudokuBoard {
+    /// Rates the board by the easiest [`Difficulty`] whose techniques finish
+    /// it without guessing.
+    ///
+    /// Unique rectangles and BUG+1 assume the board has a single solution, so
+    /// they are only used when it does. Logic can't choose between several
+    /// solutions, so a board with more than one rates as
+    /// [`Difficulty::Extreme`].
+    pub fn difficulty(&self) -> Difficulty {
+        use Difficulty::*;
+        for difficulty in [Easy, Medium, Hard, Expert] {
+            let mut techniques = up_to(difficulty);
+            if techniques
+                .iter()
+                .any(|technique| technique.assumes_unique())
+                && !self.has_unique_solution()
+            {
+                techniques.retain(|technique| !technique.assumes_unique());
+            }
+            let mut board = self.clone();
+            let (_, is_invalid) = board.reduce_with(Rules::classic(), &techniques);
+            if !is_invalid && board.is_complete() {
+                return difficulty;
+            }
+        }
+        Extreme
+    }
+}
+
+type LineOf = fn(usize) -> SudokuRegion;
+
+fn unfilled_cells(board: &SudokuBoard, cells: &[BoardPosition]) -> Vec<BoardPosition> {
+    cells
+        .iter()
+        .copied()
+        .filter(|&pos| board[pos].is_none())
+        .collect()
+}
+
+/// Every way of choosing `size` items from `items`, keeping their order.
+fn combinations<T: Copy>(items: &[T], size: usize) -> Vec<Vec<T>> {
+    if size == 0 {
+        return vec![Vec::new()];
+    }
+    (0..items.len())
+        .flat_map(|i| {
+            combinations(&items[i + 1..], size - 1)
+                .into_iter()
+                .map(move |mut rest| {
+                    rest.insert(0, items[i]);
+                    rest
+                })
+        })
+        .collect()
+}
+
+/// When `size` cells in a region have only `size` candidates between them,
+/// those cells must hold exactly those values, so no other cell in the region
+/// can take any of them.
+fn naked_subsets(
+    size: usize,
+    rules: &Rules,
+    board: &SudokuBoard,
+    possibilities_board: &mut PossibilitySpaceBoard,
+) -> bool {
+    let mut adjusted = false;
+    for (_, cells) in rules.houses() {
+        let cells = unfilled_cells(board, cells);
+        // a cell with more candidates than the subset can't be part of it
+        let small = cells
+            .iter()
+            .copied()
+            .filter(|&pos| (2..=size).contains(&possibilities_board[pos].len()))
+            .collect::<Vec<_>>();
+        for subset in combinations(&small, size) {
+            let values = subset.iter().fold(SudokuChoices::none(), |values, &pos| {
+                values | possibilities_board[pos]
+            });
+            if values.len() != size {
+                continue;
+            }
+            for &pos in cells.iter().filter(|pos| !subset.contains(pos)) {
+                let narrowed = possibilities_board[pos] & !values;
+                adjusted |= narrowed != possibilities_board[pos];
+                possibilities_board[pos] = narrowed;
+            }
+        }
+    }
+    adjusted
+}
+
+/// Two cells in a region sharing the same two candidates must hold those two
+/// values between them, so no other cell in the region can take either.
+pub(crate) fn naked_pairs(
+    rules: &Rules,
+    board: &SudokuBoard,
+    possibilities_board: &mut PossibilitySpaceBoard,
+) -> bool {
+    naked_subsets(2, rules, board, possibilities_board)
+}
+
+/// Three cells in a region with only three candidates between them, each
+/// having two or three, must hold those values, as with naked pairs.
+pub(crate) fn naked_triples(
+    rules: &Rules,
+    board: &SudokuBoard,
+    possibilities_board: &mut PossibilitySpaceBoard,
+) -> bool {
+    naked_subsets(3, rules, board, possibilities_board)
+}
+
+/// When `size` values can only go in the same `size` cells of a region, those
+/// cells must hold exactly those values, so neither can take any other value.
+fn hidden_subsets(
+    size: usize,
+    rules: &Rules,
+    board: &SudokuBoard,
+    possibilities_board: &mut PossibilitySpaceBoard,
+) -> bool {
+    let mut adjusted = false;
+    for (_, cells) in rules.houses() {
+        let cells = unfilled_cells(board, cells);
+        let places = |possibilities_board: &PossibilitySpaceBoard, space| {
+            cells
+                .iter()
+                .copied()
+                .filter(|&pos| possibilities_board[pos][space])
+                .collect::<Vec<_>>()
+        };
+        // a value with more places than the subset can't be part of it
+        let confined = Space::all()
+            .into_iter()
+            .filter(|&space| (2..=size).contains(&places(possibilities_board, space).len()))
+            .collect::<Vec<_>>();
+        for subset in combinations(&confined, size) {
+            let mut subset_places = subset
+                .iter()
+                .flat_map(|&space| places(possibilities_board, space))
+                .collect::<Vec<_>>();
+            subset_places.sort();
+            subset_places.dedup();
+            if subset_places.len() != size {
+                continue;
+            }
+            let values = subset.iter().fold(SudokuChoices::none(), |values, &space| {
+                values | SudokuChoices::one(space)
+            });
+            for &pos in &subset_places {
+                let narrowed = possibilities_board[pos] & values;
+                adjusted |= narrowed != possibilities_board[pos];
+                possibilities_board[pos] = narrowed;
+            }
+        }
+    }
+    adjusted
+}
+
+/// Two values that can only go in the same two cells of a region must fill
+/// those cells, so neither cell can take any other value.
+pub(crate) fn hidden_pairs(
+    rules: &Rules,
+    board: &SudokuBoard,
+    possibilities_board: &mut PossibilitySpaceBoard,
+) -> bool {
+    hidden_subsets(2, rules, board, possibilities_board)
+}
+
+/// Three values confined to the same three cells of a region must fill them,
+/// as with hidden pairs.
+pub(crate) fn hidden_triples(
+    rules: &Rules,
+    board: &SudokuBoard,
+    possibilities_board: &mut PossibilitySpaceBoard,
+) -> bool {
+    hidden_subsets(3, rules, board, possibilities_board)
+}
+
+/// When every place for a value within a box lies on one row or column, the
+/// value must go there, so it can't go anywhere else on that line; likewise
+/// when every place on a line lies within one box, or more generally whenever
+/// one region's places for a value all lie in another region.
+pub(crate) fn pointing(
+    rules: &Rules,
+    board: &SudokuBoard,
+    possibilities_board: &mut PossibilitySpaceBoard,
+) -> bool {
+    let mut adjusted = false;
+    for (region, cells) in rules.houses() {
+        let cells = unfilled_cells(board, cells);
+        for space in Space::all() {
+            let places = cells
+                .iter()
+                .copied()
+                .filter(|&pos| possibilities_board[pos][space])
+                .collect::<Vec<_>>();
+            let Some(&first) = places.first() else {
+                continue;
+            };
+            for (other, other_cells) in rules.houses_of(first) {
+                if other == region || !places.iter().all(|pos| other_cells.contains(pos)) {
+                    continue;
+                }
+                for &pos in other_cells
+                    .iter()
+                    .filter(|&pos| !cells.contains(pos) && board[*pos].is_none())
+                {
+                    if possibilities_board[pos][space] {
+                        possibilities_board[pos].set(space, false);
+                        adjusted = true;
+                    }
+                }
+            }
+        }
+    }
+    adjusted
+}
+
+/// When a value can only go within the same `size` columns on each of `size`
+/// rows, those rows take it once in each of those columns, so it can't go
+/// anywhere else in them; and the same with rows and columns swapped.
+fn fish(size: usize, board: &SudokuBoard, possibilities_board: &mut PossibilitySpaceBoard) -> bool {
+    let mut adjusted = false;
+    let orientations: [(LineOf, LineOf); 2] = [(Row, Column), (Column, Row)];
+    for space in Space::all() {
+        for (line, cross) in orientations {
+            // offsets along each line where the value can go
+            let places = (0..SIDE)
+                .map(|i| {
+                    line(i)
+                        .into_iter()
+                        .enumerate()
+                        .filter(|&(_, pos)| board[pos].is_none() && possibilities_board[pos][space])
+                        .map(|(j, _)| j)
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+            let base_lines = (0..SIDE)
+                .filter(|&i| (2..=size).contains(&places[i].len()))
+                .collect::<Vec<_>>();
+            for base in combinations(&base_lines, size) {
+                let mut cover = base
+                    .iter()
+                    .flat_map(|&i| places[i].iter().copied())
+                    .collect::<Vec<_>>();
+                cover.sort();
+                cover.dedup();
+                if cover.len() != size {
+                    continue;
+                }
+                for j in cover {
+                    for (i, pos) in cross(j).into_iter().enumerate() {
+                        if !base.contains(&i)
+                            && board[pos].is_none()
+                            && possibilities_board[pos][space]
+                        {
+                            possibilities_board[pos].set(space, false);
+                            adjusted = true;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    adjusted
+}
+
+/// A value confined to the same two columns on two rows must take opposite
+/// corners of that rectangle.
+pub(crate) fn x_wing(
+    _rules: &Rules,
+    board: &SudokuBoard,
+    possibilities_board: &mut PossibilitySpaceBoard,
+) -> bool {
+    fish(2, board, possibilities_board)
+}
+
+/// X-Wing over three rows and three columns.
+pub(crate) fn swordfish(
+    _rules: &Rules,
+    board: &SudokuBoard,
+    possibilities_board: &mut PossibilitySpaceBoard,
+) -> bool {
+    fish(3, board, possibilities_board)
+}
+
+/// A pivot cell with candidates XY sees two pincers with candidates XZ and YZ.
+/// Whichever value the pivot takes, one pincer must be Z, so Z can't go in any
+/// cell that sees both pincers.
+pub(crate) fn xy_wing(
+    rules: &Rules,
+    board: &SudokuBoard,
+    possibilities_board: &mut PossibilitySpaceBoard,
+) -> bool {
+    let mut adjusted = false;
+    let bivalue = |possibilities_board: &PossibilitySpaceBoard, pos: BoardPosition| {
+        board[pos].is_none() && possibilities_board[pos].len() == 2
+    };
+    for pivot in SudokuBoard::iter_positions() {
+        if !bivalue(possibilities_board, pivot) {
+            continue;
+        }
+        let pivot_values = possibilities_board[pivot];
+        // peers sharing exactly one value with the pivot
+        let pincers = rules
+            .peers(pivot)
+            .iter()
+            .copied()
+            .filter(|&pos| {
+                bivalue(possibilities_board, pos)
+                    && (possibilities_board[pos] & pivot_values).len() == 1
+            })
+            .collect::<Vec<_>>();
+        for (i, &a) in pincers.iter().enumerate() {
+            for &b in &pincers[i + 1..] {
+                let (a_values, b_values) = (possibilities_board[a], possibilities_board[b]);
+                let z = a_values & b_values;
+                if a_values & pivot_values == b_values & pivot_values
+                    || z.len() != 1
+                    || !(z & pivot_values).is_empty()
+                {
+                    continue;
+                }
+                let Some(z) = z.only() else {
+                    continue;
+                };
+                let b_peers = rules.peers(b);
+                for &pos in rules.peers(a) {
+                    if pos != b
+                        && b_peers.contains(&pos)
+                        && board[pos].is_none()
+                        && possibilities_board[pos][z]
+                    {
+                        possibilities_board[pos].set(z, false);
+                        adjusted = true;
+                    }
+                }
+            }
+        }
+    }
+    adjusted
+}
+
+/// Chains together cells that see each other and have the same two candidates
+/// XY. Neighbours in the chain must take different values, so the chain
+/// alternates X and Y along its length, and a cell seeing two cells of opposite
+/// parity sees both an X and a Y, so can't be either.
+pub(crate) fn remote_pairs(
+    rules: &Rules,
+    board: &SudokuBoard,
+    possibilities_board: &mut PossibilitySpaceBoard,
+) -> bool {
+    let mut adjusted = false;
+    let sees = |a: BoardPosition, b: BoardPosition| rules.peers(a).contains(&b);
+    let mut chained = Vec::<BoardPosition>::new();
+    for start in board.iter_empty() {
+        let pair = possibilities_board[start];
+        if pair.len() != 2 || chained.contains(&start) {
+            continue;
+        }
+        let cells = board
+            .iter_empty()
+            .filter(|&pos| possibilities_board[pos] == pair)
+            .collect::<Vec<_>>();
+
+        // two-color the chain through `start`
+        let mut parities: [Vec<BoardPosition>; 2] = [vec![start], Vec::new()];
+        let mut queue = vec![(start, 0)];
+        let mut consistent = true;
+        while let Some((pos, parity)) = queue.pop() {
+            for &other in cells.iter().filter(|&&other| sees(pos, other)) {
+                if parities[parity].contains(&other) {
+                    consistent = false;
+                } else if !parities[1 - parity].contains(&other) {
+                    parities[1 - parity].push(other);
+                    queue.push((other, 1 - parity));
+                }
+            }
+        }
+        chained.extend(parities.iter().flatten());
+        // shorter chains are no more than a naked pair
+        if !consistent || parities.iter().map(Vec::len).sum::<usize>() < 4 {
+            continue;
+        }
+
+        for pos in board.iter_empty() {
+            if (possibilities_board[pos] & pair).is_empty()
+                || parities.iter().flatten().any(|&cell| cell == pos)
+                || !parities
+                    .iter()
+                    .all(|cells| cells.iter().any(|&cell| sees(pos, cell)))
+            {
+                continue;
+            }
+            possibilities_board[pos] = possibilities_board[pos] & !pair;
+            adjusted = true;
+        }
+    }
+    adjusted
+}
+
+/// Links the cells of every region where a value has exactly two places and
+/// colors each chain of links in alternation, so one color holds the value
+/// throughout and the other doesn't. A color with two cells that see each
+/// other must be the one without it, and a cell that sees both colors can't
+/// hold it either.
+pub(crate) fn simple_coloring(
+    rules: &Rules,
+    board: &SudokuBoard,
+    possibilities_board: &mut PossibilitySpaceBoard,
+) -> bool {
+    let mut adjusted = false;
+    for space in Space::all() {
+        let holds = |possibilities_board: &PossibilitySpaceBoard, pos: BoardPosition| {
+            board[pos].is_none() && possibilities_board[pos][space]
+        };
+        let mut links = Vec::new();
+        for (_, cells) in rules.houses() {
+            if let [a, b] = cells
+                .iter()
+                .copied()
+                .filter(|&pos| holds(possibilities_board, pos))
+                .collect::<Vec<_>>()[..]
+            {
+                links.push((a, b));
+            }
+        }
+
+        let mut colored = Vec::<BoardPosition>::new();
+        for &(start, _) in &links {
+            if colored.contains(&start) {
+                continue;
+            }
+            // two-color the chain through `start`
+            let mut colors: [Vec<BoardPosition>; 2] = [vec![start], Vec::new()];
+            let mut queue = vec![(start, 0)];
+            let mut consistent = true;
+            while let Some((pos, color)) = queue.pop() {
+                for &(a, b) in &links {
+                    let other = match pos {
+                        _ if a == pos => b,
+                        _ if b == pos => a,
+                        _ => continue,
+                    };
+                    if colors[color].contains(&other) {
+                        consistent = false;
+                    } else if !colors[1 - color].contains(&other) {
+                        colors[1 - color].push(other);
+                        queue.push((other, 1 - color));
+                    }
+                }
+            }
+            colored.extend(colors.iter().flatten());
+            if !consistent {
+                continue;
+            }
+
+            let sees = |a: BoardPosition, b: BoardPosition| rules.peers(a).contains(&b);
+            let wrapped = colors.iter().position(|cells| {
+                cells
+                    .iter()
+                    .enumerate()
+                    .any(|(i, &a)| cells[i + 1..].iter().any(|&b| sees(a, b)))
+            });
+            if let Some(wrapped) = wrapped {
+                for &pos in &colors[wrapped] {
+                    possibilities_board[pos].set(space, false);
+                }
+                adjusted = true;
+                continue;
+            }
+            for pos in SudokuBoard::iter_positions() {
+                if holds(possibilities_board, pos)
+                    && !colors.iter().flatten().any(|&cell| cell == pos)
+                    && colors
+                        .iter()
+                        .all(|cells| cells.iter().any(|&cell| sees(pos, cell)))
+                {
+                    possibilities_board[pos].set(space, false);
+                    adjusted = true;
+                }
+            }
+        }
+    }
+    adjusted
+}
+
+/// Follows chains of inferences about one value, alternating between strong
+/// links (the only two places for the value in a region, so if one lacks it
+/// the other has it) and weak links (peers, so if one has it the other
+/// doesn't). Assuming a cell lacks the value, every cell the chain then shows
+/// must have it pairs with the first: one of the two holds the value, so no
+/// cell seeing both can. A chain that leads back to the first cell holding
+/// the value shows it must. This covers the eliminations of every nice loop,
+/// continuous or not.
+pub(crate) fn x_cycles(
+    rules: &Rules,
+    board: &SudokuBoard,
+    possibilities_board: &mut PossibilitySpaceBoard,
+) -> bool {
+    let mut adjusted = false;
+    for space in Space::all() {
+        let holds = |possibilities_board: &PossibilitySpaceBoard, pos: BoardPosition| {
+            board[pos].is_none() && possibilities_board[pos][space]
+        };
+        let mut strong_links = Vec::new();
+        for (_, cells) in rules.houses() {
+            if let [a, b] = cells
+                .iter()
+                .copied()
+                .filter(|&pos| holds(possibilities_board, pos))
+                .collect::<Vec<_>>()[..]
+            {
+                strong_links.push((a, b));
+            }
+        }
+        if strong_links.is_empty() {
+            continue;
+        }
+
+        for start in SudokuBoard::iter_positions() {
+            if !holds(possibilities_board, start) {
+                continue;
+            }
+            // every (cell, holds the value) that follows from `start` lacking it
+            let mut reached = vec![(start, false)];
+            let mut queue = vec![(start, false)];
+            while let Some((pos, value)) = queue.pop() {
+                let next = match value {
+                    false => strong_links
+                        .iter()
+                        .filter_map(|&(a, b)| match pos {
+                            _ if a == pos => Some(b),
+                            _ if b == pos => Some(a),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>(),
+                    true => rules
+                        .peers(pos)
+                        .iter()
+                        .copied()
+                        .filter(|&other| holds(possibilities_board, other))
+                        .collect(),
+                };
+                for other in next {
+                    if !reached.contains(&(other, !value)) {
+                        reached.push((other, !value));
+                        queue.push((other, !value));
+                    }
+                }
+            }
+
+            if reached.contains(&(start, true)) {
+                possibilities_board[start] = SudokuChoices::one(space);
+                adjusted = true;
+                continue;
+            }
+            let sees = |a: BoardPosition, b: BoardPosition| rules.peers(a).contains(&b);
+            for &(end, _) in reached
+                .iter()
+                .filter(|&&(end, value)| value && end != start)
+            {
+                for &pos in rules.peers(start) {
+                    if pos != end && sees(pos, end) && holds(possibilities_board, pos) {
+                        possibilities_board[pos].set(space, false);
+                        adjusted = true;
+                    }
+                }
+            }
+        }
+    }
+    adjusted
+}
+
+/// Four cells at the corners of a rectangle spanning two rows, two columns
+/// and two squares, all with candidates XY, could swap X and Y and leave a
+/// second solution. A proper puzzle has only one, so when three corners are
+/// exactly XY the fourth must be something else. Assumes a unique solution.
+pub(crate) fn unique_rectangle(
+    rules: &Rules,
+    board: &SudokuBoard,
+    possibilities_board: &mut PossibilitySpaceBoard,
+) -> bool {
+    // swapping values within rows, columns and squares can still break a
+    // variant rule, so the pattern is only deadly in classic sudoku
+    if !rules.constraints().is_empty() {
+        return false;
+    }
+    let mut adjusted = false;
+    for y1 in 0..SIDE {
+        for y2 in y1 + 1..SIDE {
+            for x1 in 0..SIDE {
+                for x2 in x1 + 1..SIDE {
+                    // two squares means sharing either a band or a stack
+                    let same_band = y1 / BOX_SIZE == y2 / BOX_SIZE;
+                    let same_stack = x1 / BOX_SIZE == x2 / BOX_SIZE;
+                    let corners = [(x1, y1), (x2, y1), (x1, y2), (x2, y2)];
+                    if same_band == same_stack || corners.iter().any(|&pos| board[pos].is_some()) {
+                        continue;
+                    }
+                    for (i, &roof) in corners.iter().enumerate() {
+                        let others = corners
+                            .iter()
+                            .enumerate()
+                            .filter(|&(j, _)| j != i)
+                            .map(|(_, &pos)| possibilities_board[pos])
+                            .collect::<Vec<_>>();
+                        let pair = others[0];
+                        let candidates = possibilities_board[roof];
+                        if pair.len() == 2
+                            && others.iter().all(|&other| other == pair)
+                            && candidates.len() > 2
+                            && candidates & pair == pair
+                        {
+                            possibilities_board[roof] = candidates & !pair;
+                            adjusted = true;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    adjusted
+}
+
+/// With every empty cell down to two candidates, each appearing twice in
+/// every region, the candidates could be swapped around to give a second
+/// solution. When a single cell has a third candidate, that cell must hold
+/// whichever of its values appears an odd number of times in its regions to
+/// avoid this. Assumes a unique solution.
+pub(crate) fn bug_plus_one(
+    rules: &Rules,
+    board: &SudokuBoard,
+    possibilities_board: &mut PossibilitySpaceBoard,
+) -> bool {
+    // as with unique rectangles, variant rules can rule out the swap
+    if !rules.constraints().is_empty() {
+        return false;
+    }
+    let mut trivalue = None;
+    for pos in board.iter_empty() {
+        match possibilities_board[pos].len() {
+            2 => {}
+            3 if trivalue.is_none() => trivalue = Some(pos),
+            _ => return false,
+        }
+    }
+    let Some(pos) = trivalue else {
+        return false;
+    };
+    let odd = possibilities_board[pos]
+        .iter()
+        .filter(|&space| {
+            rules.houses_of(pos).all(|(_, cells)| {
+                let places = cells
+                    .iter()
+                    .filter(|&&cell| board[cell].is_none() && possibilities_board[cell][space])
+                    .count();
+                places % 2 == 1
+            })
+        })
+        .collect::<Vec<_>>();
+    let [space] = odd[..] else {
+        return false;
+    };
+    possibilities_board[pos] = SudokuChoices::one(space);
+    true
+}
+
+/// Asserts that the techniques up to `level`, less `needed`, stall on
+/// `board`, and that the sound ones among them finish it with `needed` added.
+/// Returns the stalled board and its candidates for closer checks.
+#[cfg(test)]
+fn assert_needs(
+    board: &SudokuBoard,
+    level: Difficulty,
+    needed: &[Technique],
+) -> (SudokuBoard, PossibilitySpaceBoard) {
+    let others = up_to(level)
+        .into_iter()
+        .filter(|technique| !needed.contains(technique))
+        .collect::<Vec<_>>();
+    let mut stalled = board.clone();
+    let (possibilities_board, _) = stalled.reduce_with(Rules::classic(), &others);
+    assert!(!stalled.is_complete(), "{board} doesn't need {needed:?}");
+
+    let finishing = others
+        .into_iter()
+        .filter(|technique| !technique.assumes_unique())
+        .chain(needed.iter().copied())
+        .collect::<Vec<_>>();
+    let mut board = board.clone();
+    let (_, is_invalid) = board.reduce_with(Rules::classic(), &finishing);
+    assert!(!is_invalid);
+    assert!(board.is_solution());
+    assert_eq!(board.validate(), Ok(()));
+    (stalled, possibilities_board)
+}
+
+#[test]
+fn test_naked_pairs() {
+    // singles alone stall on this board
+    let board: SudokuBoard =
+        "9..4...8......8.2.7.1......1.5..3..2...9..4..2.6....9..5.6...3...28....6....72..."
+            .parse()
+            .unwrap();
+    assert_needs(
+        &board,
+        Difficulty::Medium,
+        &[Technique::NakedPairs, Technique::HiddenPairs],
+    );
+}
+
+#[test]
+fn test_hidden_pairs() {
+    use crate::Space;
+
+    let mut board: SudokuBoard =
+        ".7..61......2...5.3.....7.......6..9.5..2....1....843..457..1....2...96...9.3...."
+            .parse()
+            .unwrap();
+    let (possibilities_board, is_invalid) = board.reduce();
+    assert!(!is_invalid);
+    // 3 and 7 can only go in these two cells of column 5
+    let pair = SudokuChoices::one(Space::Three) | SudokuChoices::one(Space::Seven);
+    assert_eq!(possibilities_board[(5, 1)], pair);
+    assert_eq!(possibilities_board[(5, 4)], pair);
+}
+
+#[test]
+fn test_pointing() {
+    // singles and pairs alone stall on this board
+    let board: SudokuBoard =
+        "...2..74.45.6...9..1.......39...5..71.53..........4......47.5.6..7593............"
+            .parse()
+            .unwrap();
+    assert_needs(
+        &board,
+        Difficulty::Hard,
+        &[
+            Technique::Pointing,
+            Technique::NakedTriples,
+            Technique::HiddenTriples,
+        ],
+    );
+}
+
+#[test]
+fn test_naked_triples() {
+    // singles, pairs and pointing alone stall on this board
+    let board: SudokuBoard =
+        "............4..2.8.5..18.7..28...5....42...16....5.9..3..5....1....9.7.37.6.8...."
+            .parse()
+            .unwrap();
+    assert_needs(
+        &board,
+        Difficulty::Hard,
+        &[Technique::NakedTriples, Technique::HiddenTriples],
+    );
+}
+
+#[test]
+fn test_hidden_triples() {
+    // every other technique up to hard stalls on this board
+    let board: SudokuBoard =
+        ".........35.....2..1...398..9.146...2.1.5..9.........8.76.....2....314..4..2....5"
+            .parse()
+            .unwrap();
+    assert_needs(&board, Difficulty::Hard, &[Technique::HiddenTriples]);
+}
+
+#[test]
+fn test_x_wing() {
+    // stalls without x-wing, even with pairs and pointing
+    let board: SudokuBoard =
+        "......5..8.75...1........9......7.6.5...92..4..81....7.......76.3...4...7.2.35..."
+            .parse()
+            .unwrap();
+    assert_needs(&board, Difficulty::Hard, &[Technique::XWing]);
+}
+
+#[test]
+fn test_swordfish() {
+    // every other technique stalls on this board
+    let board: SudokuBoard =
+        "......5827...2.1...8...4.......7..45.7.........3...9......5...6.362....9.2586...1"
+            .parse()
+            .unwrap();
+    assert_needs(&board, Difficulty::Expert, &[Technique::Swordfish]);
+}
+
+#[test]
+fn test_xy_wing() {
+    // every other technique, x-wing included, stalls on this board, though
+    // x-cycles find another way through
+    let board: SudokuBoard =
+        "..........51..9....96.5....5....7.49...32.8.57....81..8...6.4.2.....1..8.7......."
+            .parse()
+            .unwrap();
+    assert_needs(
+        &board,
+        Difficulty::Expert,
+        &[Technique::XYWing, Technique::XCycles],
+    );
+}
+
+#[test]
+fn test_remote_pairs() {
+    // every other technique stalls on this board, short of coloring and
+    // x-cycles, which follow the same chain a value at a time
+    let board: SudokuBoard =
+        "......3....839.6.7.5.6...2451.......9.7.....264.5.2......92.....3....7...6...1..."
+            .parse()
+            .unwrap();
+    let (stalled, mut possibilities_board) = assert_needs(
+        &board,
+        Difficulty::Expert,
+        &[
+            Technique::RemotePairs,
+            Technique::SimpleColoring,
+            Technique::XCycles,
+        ],
+    );
+    let solution = board.solve().expect("Sudoku board has a solution");
+    assert!(remote_pairs(
+        Rules::classic(),
+        &stalled,
+        &mut possibilities_board
+    ));
+    assert!(
+        SudokuBoard::iter_positions().all(|pos| possibilities_board[pos][solution[pos].unwrap()])
+    );
+
+    let (_, steps) = board.solve_with_steps();
+    assert!(steps
+        .iter()
+        .any(|step| step.reason == crate::StepReason::Technique(Technique::RemotePairs)));
+}
+
+#[test]
+fn test_simple_coloring() {
+    // every other technique, the wings included, stalls on this board, short
+    // of x-cycles, which find every elimination coloring does
+    let board: SudokuBoard =
+        ".....1..8.1....7....7..52.1..1.72....7..5..8.3..9..6..9....43.5..3....9...8.....6"
+            .parse()
+            .unwrap();
+    assert_needs(
+        &board,
+        Difficulty::Expert,
+        &[Technique::SimpleColoring, Technique::XCycles],
+    );
+
+    let (_, steps) = board.solve_with_steps();
+    assert!(steps
+        .iter()
+        .any(|step| step.reason == crate::StepReason::Technique(Technique::SimpleColoring)));
+}
+
+#[test]
+fn test_x_cycles() {
+    // every other technique, coloring included, stalls on this board
+    let board: SudokuBoard =
+        "......9..3......62..1..3......5..249.7.3.9.......68.7.......7.12....1...6.9...4.5"
+            .parse()
+            .unwrap();
+    let (stalled, mut possibilities_board) =
+        assert_needs(&board, Difficulty::Expert, &[Technique::XCycles]);
+    let solution = board.solve().expect("Sudoku board has a solution");
+    assert!(x_cycles(
+        Rules::classic(),
+        &stalled,
+        &mut possibilities_board
+    ));
+    assert!(
+        SudokuBoard::iter_positions().all(|pos| possibilities_board[pos][solution[pos].unwrap()])
+    );
+
+    let (_, steps) = board.solve_with_steps();
+    assert!(steps
+        .iter()
+        .any(|step| step.reason == crate::StepReason::Technique(Technique::XCycles)));
+}
+
+#[test]
+fn test_unique_rectangle() {
+    // every other technique stalls on this board
+    let board: SudokuBoard =
+        ".........165.2......3..1..4.81.......3..1.7.....9...867.2.569.3.....9...6...3..2."
+            .parse()
+            .unwrap();
+    assert_needs(&board, Difficulty::Expert, &[Technique::UniqueRectangle]);
+
+    let (_, steps) = board.solve_with_steps();
+    assert!(steps
+        .iter()
+        .any(|step| step.reason == crate::StepReason::Technique(Technique::UniqueRectangle)));
+
+    // without a given the board has other solutions, which the rectangle
+    // would rule out, so only sound logic is shown
+    let mut ambiguous = board.clone();
+    ambiguous[(1, 1)] = None;
+    assert_eq!(ambiguous.count_solutions(2), 2);
+    assert_eq!(ambiguous.difficulty(), Difficulty::Extreme);
+    let (solution, steps) = ambiguous.solve_with_steps();
+    assert!(solution.is_solution());
+    for step in &steps {
+        assert!(
+            !matches!(step.reason, crate::StepReason::Technique(technique) if technique.assumes_unique())
+        );
+        if let crate::StepAction::Eliminate(eliminated) = step.action {
+            assert!(!eliminated[solution[step.pos].unwrap()]);
+        }
+    }
+}
+
+#[test]
+fn test_bug_plus_one() {
+    // every other technique stalls on this board, short of the last step
+    let board: SudokuBoard =
+        "........8..8.6.72.....87..3.9..4..724......5.71...3....4.6..215..2.3..4.6.1......"
+            .parse()
+            .unwrap();
+    let (stalled, possibilities_board) =
+        assert_needs(&board, Difficulty::Expert, &[Technique::BugPlusOne]);
+    let trivalue = stalled
+        .iter_empty()
+        .filter(|&pos| possibilities_board[pos].len() == 3)
+        .collect::<Vec<_>>();
+    assert_eq!(trivalue.len(), 1);
+    assert!(stalled
+        .iter_empty()
+        .all(|pos| pos == trivalue[0] || possibilities_board[pos].len() == 2));
+
+    let (_, steps) = board.solve_with_steps();
+    assert!(steps
+        .iter()
+        .any(|step| step.reason == crate::StepReason::Technique(Technique::BugPlusOne)));
+}
+
+#[test]
+fn test_difficulty() {
+    use Difficulty::*;
+
+    let rated_boards = [
+        // test_reduction
+        (
+            "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+            Easy,
+        ),
+        // the other technique tests
+        (
+            "9..4...8......8.2.7.1......1.5..3..2...9..4..2.6....9..5.6...3...28....6....72...",
+            Medium,
+        ),
+        (
+            "...2..74.45.6...9..1.......39...5..71.53..........4......47.5.6..7593............",
+            Hard,
+        ),
+        (
+            "......5..8.75...1........9......7.6.5...92..4..81....7.......76.3...4...7.2.35...",
+            Expert,
+        ),
+        // test_solve_hard through test_solve_hard_4
+        (
+            "2..5.74.6....31.........23.....2....86.31.....45........9...7....695...2..1..6..8",
+            Hard,
+        ),
+        (
+            "..65.....7.5..23...3.....8..5..96.7.1.4.....8...82.....2.....9...72..4.......75..",
+            Extreme,
+        ),
+        (
+            ".293.84565782.61.9...1.5.7.3.5.2.6.......9.4..91.67....3..5.........29.39.7....24",
+            Easy,
+        ),
+        // simple coloring finishes this one
+        (
+            "5.8427....4..1.7..19...3..2....6...57.....2..6.513.9..9....15......4..2..7......8",
+            Expert,
+        ),
+    ];
+    for (board_str, difficulty) in rated_boards {
+        let board: SudokuBoard = board_str.parse().unwrap();
+        assert_eq!(board.difficulty(), difficulty, "{board_str}");
+    }
+}
@@ -0,0 +1,230 @@
+use crate::{Board, Space, SudokuBoard, BOX_SIZE, CELLS, SIDE};
+
+/// Every ordering of `0..n`.
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![Vec::new()];
+    }
+    permutations(n - 1)
+        .into_iter()
+        .flat_map(|perm| {
+            (0..n).map(move |i| {
+                let mut perm = perm.clone();
+                perm.insert(i, n - 1);
+                perm
+            })
+        })
+        .collect()
+}
+
+/// Every reordering of rows (or columns) that keeps a valid board valid:
+/// the bands in any order, then the lines within each band in any order.
+fn line_orders() -> Vec<[usize; SIDE]> {
+    let perms = permutations(BOX_SIZE);
+    let mut orders = Vec::new();
+    for bands in &perms {
+        let mut partial = vec![Vec::new()];
+        for &band in bands {
+            partial = partial
+                .into_iter()
+                .flat_map(|order: Vec<usize>| {
+                    perms.iter().map(move |lines| {
+                        let mut order = order.clone();
+                        order.extend(lines.iter().map(|line| band * BOX_SIZE + line));
+                        order
+                    })
+                })
+                .collect();
+        }
+        orders.extend(
+            partial
+                .into_iter()
+                .map(|order| <[usize; SIDE]>::try_from(order).expect("one entry per line")),
+        );
+    }
+    orders
+}
+
+impl SudokuBoard {
+    /// The board turned a quarter turn clockwise.
+    pub fn rotate90(&self) -> SudokuBoard {
+        Board::from_fn(|(x, y)| self[(y, SIDE - 1 - x)])
+    }
+
+    /// The board turned upside down.
+    pub fn rotate180(&self) -> SudokuBoard {
+        Board::from_fn(|(x, y)| self[(SIDE - 1 - x, SIDE - 1 - y)])
+    }
+
+    /// The board mirrored left to right.
+    pub fn reflect_horizontal(&self) -> SudokuBoard {
+        Board::from_fn(|(x, y)| self[(SIDE - 1 - x, y)])
+    }
+
+    /// The board mirrored top to bottom.
+    pub fn reflect_vertical(&self) -> SudokuBoard {
+        Board::from_fn(|(x, y)| self[(x, SIDE - 1 - y)])
+    }
+
+    /// The board with every value replaced by `mapping[value - 1]`. Only a
+    /// permutation of the values keeps a valid board valid.
+    pub fn relabel(&self, mapping: [Space; SIDE]) -> SudokuBoard {
+        Board::from_fn(|pos| self[pos].map(|space| mapping[space.idx()]))
+    }
+
+    /// A representative shared by every board that is the same puzzle up to
+    /// relabelling the values, transposing, and swapping bands, stacks, or
+    /// rows and columns within them.
+    ///
+    /// It is the smallest such board read as digits in reading order, with
+    /// empty cells as 0 and values relabelled in order of first appearance.
+    pub fn canonical(&self) -> SudokuBoard {
+        let orders = line_orders();
+        let transposed = Board::from_fn(|(x, y)| self[(y, x)]);
+
+        let mut best = [u8::MAX; CELLS];
+        for board in [self, &transposed] {
+            for rows in &orders {
+                for columns in &orders {
+                    let mut labels = [0; SIDE];
+                    let mut next_label = 0;
+                    let mut smaller = false;
+                    for (i, best) in best.iter_mut().enumerate() {
+                        let label = match board[(columns[i % SIDE], rows[i / SIDE])] {
+                            None => 0,
+                            Some(space) => {
+                                let label = &mut labels[space.idx()];
+                                if *label == 0 {
+                                    next_label += 1;
+                                    *label = next_label;
+                                }
+                                *label
+                            }
+                        };
+                        if !smaller {
+                            if label > *best {
+                                break;
+                            }
+                            smaller = label < *best;
+                        }
+                        if smaller {
+                            *best = label;
+                        }
+                    }
+                }
+            }
+        }
+
+        Board::from_fn(|(x, y)| match best[y * SIDE + x] {
+            0 => None,
+            label => Some(Space::all()[label as usize - 1]),
+        })
+    }
+
+    /// A hash of [`SudokuBoard::canonical`], shared by every equivalent board
+    /// and suitable as a deduplication key in storage. It is FNV-1a over the
+    /// canonical digits, so it stays the same across platforms and Rust
+    /// versions, unlike [`std::hash::Hash`].
+    pub fn fingerprint(&self) -> u64 {
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+        self.canonical()
+            .iter()
+            .map(|space| space.map_or(0, |space| usize::from(space) as u8))
+            .fold(FNV_OFFSET_BASIS, |hash, digit| {
+                (hash ^ u64::from(digit)).wrapping_mul(FNV_PRIME)
+            })
+    }
+}
+
+#[test]
+fn test_canonical() {
+    let board = crate::easy_puzzle();
+    let canonical = board.canonical();
+    assert_eq!(canonical.canonical(), canonical);
+    assert_eq!(canonical.clue_count(), board.clue_count());
+    assert!(canonical
+        .solve()
+        .is_some_and(|solution| solution.validate() == Ok(())));
+
+    let rotated = board.rotate90();
+    assert_ne!(rotated, board);
+    assert_eq!(rotated.canonical(), canonical);
+
+    // swapping the first two bands, then mirroring and relabelling
+    let mut mapping = Space::all();
+    mapping.rotate_left(1);
+    let transformed = Board::from_fn(|(x, y)| {
+        let y = if y < 2 * BOX_SIZE {
+            (y + BOX_SIZE) % (2 * BOX_SIZE)
+        } else {
+            y
+        };
+        board[(x, y)]
+    })
+    .reflect_vertical()
+    .relabel(mapping);
+    assert_eq!(transformed.canonical(), canonical);
+
+    let mut different = board.clone();
+    different[(0, 0)] = None;
+    assert_ne!(different.canonical(), canonical);
+}
+
+#[test]
+fn test_fingerprint() {
+    let solution = crate::easy_solution();
+    let fingerprint = solution.fingerprint();
+    assert_eq!(fingerprint, solution.clone().fingerprint());
+
+    let mut mapping = Space::all();
+    mapping.rotate_left(4);
+    let equivalent = solution.rotate90().relabel(mapping);
+    assert_ne!(equivalent, solution);
+    assert_eq!(equivalent.fingerprint(), fingerprint);
+
+    // a highly regular grid no transformation of the first one can reach
+    let different: SudokuBoard =
+        "123456789456789123789123456214365897365897214897214365531642978642978531978531642"
+            .parse()
+            .unwrap();
+    assert_eq!(different.validate(), Ok(()));
+    assert_ne!(different.fingerprint(), fingerprint);
+}
+
+#[test]
+fn test_transforms() {
+    let board = crate::easy_puzzle();
+    let solution = board.solve().expect("Sudoku board has a solution");
+
+    assert_eq!(board.rotate90().rotate90().rotate90().rotate90(), board);
+    assert_eq!(board.rotate90().rotate90(), board.rotate180());
+    assert_eq!(board.reflect_horizontal().reflect_horizontal(), board);
+    assert_eq!(
+        board.reflect_horizontal().reflect_vertical(),
+        board.rotate180()
+    );
+    // the top left corner moves to the top right
+    assert_eq!(board.rotate90()[(SIDE - 1, 0)], board[(0, 0)]);
+    assert_eq!(board.reflect_horizontal()[(SIDE - 1, 0)], board[(0, 0)]);
+    assert_eq!(board.reflect_vertical()[(0, SIDE - 1)], board[(0, 0)]);
+
+    let mut mapping = Space::all();
+    mapping.reverse();
+    let relabelled = board.relabel(mapping);
+    assert_eq!(relabelled[(0, 0)], Some(Space::Five));
+    assert_eq!(relabelled[(1, 0)], Some(Space::Seven));
+    assert_eq!(relabelled.relabel(mapping), board);
+
+    for transformed in [
+        solution.rotate90(),
+        solution.rotate180(),
+        solution.reflect_horizontal(),
+        solution.reflect_vertical(),
+        solution.relabel(mapping),
+    ] {
+        assert_eq!(transformed.validate(), Ok(()));
+        assert_eq!(transformed.clue_count(), CELLS);
+    }
+    assert_eq!(board.rotate90().solve(), Some(solution.rotate90()));
+}
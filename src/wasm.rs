@@ -0,0 +1,42 @@
+//! Browser bindings, taking and returning boards as 81-character strings.
+//!
+//! Build the module with
+//! `cargo rustc --release --lib --target wasm32-unknown-unknown --features wasm --crate-type cdylib`
+//! and run `wasm-bindgen` on the result.
+use wasm_bindgen::prelude::*;
+
+use crate::SudokuBoard;
+
+/// Solves `puzzle`, or returns `None` if it doesn't parse or has no solution.
+#[wasm_bindgen]
+pub fn solve(puzzle: &str) -> Option<String> {
+    let board: SudokuBoard = puzzle.parse().ok()?;
+    let solution = board.solve()?;
+    Some(solution.to_string().replace('\n', ""))
+}
+
+/// Whether `puzzle` parses and none of its values clash.
+#[wasm_bindgen]
+pub fn validate(puzzle: &str) -> bool {
+    puzzle
+        .parse::<SudokuBoard>()
+        .is_ok_and(|board| board.validate().is_ok())
+}
+
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+#[cfg_attr(not(target_arch = "wasm32"), test)]
+fn test_wasm_bindings() {
+    let puzzle =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+    assert!(validate(puzzle));
+    assert_eq!(
+        solve(puzzle).as_deref(),
+        Some("534678912672195348198342567859761423426853791713924856961537284287419635345286179")
+    );
+
+    let conflicting = puzzle.replacen("53.", "535", 1);
+    assert!(!validate(&conflicting));
+    assert_eq!(solve(&conflicting), None);
+    assert!(!validate("not a puzzle"));
+    assert_eq!(solve("not a puzzle"), None);
+}